@@ -1,12 +1,64 @@
-use aoc::day6;
+use aoc::registry;
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-pub fn day6_benchmark(c: &mut Criterion) {
-    let input = include_str!("../src/input/day6.txt");
+/// Benchmarks parsing and both parts of every registered day, with the
+/// puzzle's own parameters, each day in its own group, so a single day can be
+/// picked with `cargo bench -- "2022 day 6/"`.
+pub fn days_benchmark(c: &mut Criterion) {
+    for day in registry::days() {
+        let model = (day.parse)(day.input).unwrap();
+        let params = (day.params)(&[]).unwrap();
 
-    c.bench_function("day 6 task 1", |b| b.iter(|| day6::task1(black_box(input))));
-    c.bench_function("day 6 task 2", |b| b.iter(|| day6::task1(black_box(input))));
+        let mut group = c.benchmark_group(day.to_string());
+        // the slowest parts take seconds, so keep to criterion's minimum
+        group.sample_size(10);
+
+        group.bench_function("parse", |b| b.iter(|| (day.parse)(black_box(day.input))));
+        group.bench_function("part 1", |b| {
+            b.iter(|| (day.part1)(black_box(&model), &params))
+        });
+        group.bench_function("part 2", |b| {
+            b.iter(|| (day.part2)(black_box(&model), &params))
+        });
+
+        group.finish();
+    }
+}
+
+/// Searches a generated stream with its markers right at the end, once as a
+/// slice and once read in chunks, to compare the two.
+#[cfg(feature = "y2022-day6")]
+pub fn day6_stream_benchmark(c: &mut Criterion) {
+    use aoc::y2022::day6::{self, UniqueSpan};
+    use criterion::Throughput;
+
+    let mut stream = b"ab".repeat(8 * 1024 * 1024);
+    stream.extend(b"abcdefghijklmn");
+    let params = day6::Params::default();
+
+    let mut group = c.benchmark_group("2022 day 6 stream");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(stream.len() as u64));
+
+    group.bench_function("slice", |b| {
+        b.iter(|| {
+            let stream = black_box(&stream[..]);
+
+            (
+                stream.find_unique_span_idx(params.packet),
+                stream.find_unique_span_idx(params.message),
+            )
+        })
+    });
+    group.bench_function("read", |b| {
+        b.iter(|| day6::markers(black_box(&stream[..]), &params).count())
+    });
+
+    group.finish();
 }
 
-criterion_group!(benches, day6_benchmark);
+#[cfg(not(feature = "y2022-day6"))]
+pub fn day6_stream_benchmark(_: &mut Criterion) {}
+
+criterion_group!(benches, days_benchmark, day6_stream_benchmark);
 criterion_main!(benches);
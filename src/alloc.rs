@@ -0,0 +1,225 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt::Display,
+    sync::atomic::{AtomicIsize, AtomicUsize, Ordering::Relaxed},
+};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+// signed, since memory allocated while uncounted can be freed while counted,
+// which takes off more than was ever added, so only differences between them
+// mean anything
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+thread_local! {
+    // const and without a destructor, so reading it never allocates
+    static UNCOUNTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Hands every request to the system allocator, while keeping count of how
+/// many allocations were made, how many bytes they asked for, and the most
+/// memory that was live at once.
+///
+/// Only counts anything once it's installed as the `#[global_allocator]`,
+/// which the binary does with the `alloc-stats` feature.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && counted() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && counted() {
+            record_alloc(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if counted() {
+            LIVE.fetch_sub(layout.size() as isize, Relaxed);
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && counted() {
+            // a grown or shrunk block counts as freeing the old one and
+            // allocating the new one
+            LIVE.fetch_sub(layout.size() as isize, Relaxed);
+            record_alloc(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+fn counted() -> bool {
+    !UNCOUNTED.try_with(Cell::get).unwrap_or(false)
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    BYTES.fetch_add(size, Relaxed);
+
+    let live = LIVE.fetch_add(size as isize, Relaxed) + size as isize;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+/// What a piece of code allocated while it ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// The most memory live at once on top of what was already live when
+    /// measuring started.
+    pub peak: usize,
+}
+
+/// Runs `f`, returning its result and what it allocated.
+///
+/// The counters are global, so anything running on other threads at the same
+/// time gets counted too, which is what we want for rayon, but it also means
+/// measurements shouldn't overlap.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = BYTES.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+
+    let res = f();
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: BYTES.load(Relaxed) - bytes,
+        peak: (PEAK.load(Relaxed) - live).max(0) as usize,
+    };
+
+    (res, stats)
+}
+
+/// Runs `f` without counting what this thread allocates meanwhile, for work
+/// that runs alongside a measurement without being part of it. Freeing that
+/// memory on a counted thread while measuring lowers the live count, so it can
+/// only make the peak come out lower than it was.
+pub fn uncounted<T>(f: impl FnOnce() -> T) -> T {
+    let previous = UNCOUNTED.with(|uncounted| uncounted.replace(true));
+    let res = f();
+    UNCOUNTED.with(|uncounted| uncounted.set(previous));
+
+    res
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            ByteSize(self.bytes),
+            ByteSize(self.peak)
+        )
+    }
+}
+
+struct ByteSize(usize);
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} {}", self.0, UNITS[0])
+        } else {
+            write!(f, "{size:.1} {}", UNITS[unit])
+        }
+    }
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod tests {
+
+    use std::sync::Mutex;
+
+    use super::*;
+    use test_case::test_case;
+
+    /// Keeps these tests' measurements from overlapping each other, since
+    /// freeing what was allocated uncounted throws off the live count.
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn test_measure() {
+        let _serial = SERIAL.lock().unwrap();
+        let (v, stats) = measure(|| {
+            let v: Vec<u8> = Vec::with_capacity(4096);
+            let scratch: Vec<u8> = vec![0; 8192];
+            drop(scratch);
+            v
+        });
+
+        assert_eq!(v.capacity(), 4096);
+        assert!(stats.allocations >= 2, "{stats:?}");
+        assert!(stats.bytes >= 4096 + 8192, "{stats:?}");
+        // other tests allocate and free alongside this one, so the peak can
+        // only be checked loosely
+        assert!(stats.peak > 0, "{stats:?}");
+    }
+
+    #[test]
+    fn test_uncounted() {
+        const SIZE: usize = 64 * 1024 * 1024;
+        let _serial = SERIAL.lock().unwrap();
+
+        let (len, stats) = measure(|| uncounted(|| vec![0_u8; SIZE].len()));
+
+        assert_eq!(len, SIZE);
+        // other tests allocate alongside this one, just not this much
+        assert!(stats.bytes < SIZE, "{stats:?}");
+    }
+
+    #[test]
+    fn test_freed_after_uncounted() {
+        const SIZE: usize = 64 * 1024 * 1024;
+        let _serial = SERIAL.lock().unwrap();
+
+        let v = uncounted(|| vec![0_u8; SIZE]);
+        let (len, stats) = measure(|| {
+            drop(v);
+            vec![0_u8; 16].len()
+        });
+
+        assert_eq!(len, 16);
+        assert!(stats.peak < SIZE, "{stats:?}");
+    }
+
+    #[test_case(0, "0 B")]
+    #[test_case(1023, "1023 B")]
+    #[test_case(1536, "1.5 KiB")]
+    #[test_case(3 * 1024 * 1024, "3.0 MiB")]
+    fn test_byte_size(bytes: usize, expected: &str) {
+        assert_eq!(ByteSize(bytes).to_string(), expected);
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt::Display;
+
+/// What a day's part comes up with. Most puzzles want a number, some want a
+/// word, and some draw their answer as a picture that has to be read by eye.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(u64),
+    Signed(i64),
+    Text(String),
+    /// Multi-line drawing, such as the CRT output of day 10.
+    Image(String),
+}
+
+impl Answer {
+    pub fn is_image(&self) -> bool {
+        matches!(self, Answer::Image(_))
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::Integer(v as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::Integer(v)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(v: isize) -> Self {
+        Answer::Signed(v as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Signed(v)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(v) => write!(f, "{v}"),
+            Answer::Signed(v) => write!(f, "{v}"),
+            Answer::Text(s) | Answer::Image(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Answer::from(42_usize), Answer::Integer(42) ; "usize")]
+    #[test_case(Answer::from(-42_isize), Answer::Signed(-42) ; "isize")]
+    #[test_case(Answer::from("CMZ"), Answer::Text("CMZ".to_string()) ; "str")]
+    fn test_from(answer: Answer, expected: Answer) {
+        assert_eq!(answer, expected);
+    }
+
+    #[test_case(Answer::Integer(24933642), "24933642")]
+    #[test_case(Answer::Signed(-7), "-7")]
+    #[test_case(Answer::Text("MCD".to_string()), "MCD")]
+    #[test_case(Answer::Image("#.\n.#".to_string()), "#.\n.#")]
+    fn test_display(answer: Answer, expected: &str) {
+        assert_eq!(answer.to_string(), expected);
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use serde_json::Value;
+
+use crate::prelude::*;
+
+/// The directory criterion keeps its results in, for the latest run under
+/// `new` and for every saved baseline under its own name.
+pub fn criterion_dir() -> PathBuf {
+    let target = env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(target)
+        .join("criterion")
+}
+
+/// How a single benchmark's mean time changed between a baseline and the
+/// latest run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delta {
+    pub id: String,
+    pub baseline: Duration,
+    pub current: Duration,
+    /// Whether the benchmark was left out of the latest run, so its result is
+    /// from an earlier one.
+    pub stale: bool,
+}
+
+/// The longest criterion goes between writing the results of two benchmarks
+/// of the same run. Results written further apart than this are from different
+/// runs.
+const RUN_GAP: Duration = Duration::from_secs(5 * 60);
+
+impl Delta {
+    /// The change in percent, positive when it got slower.
+    pub fn change(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+
+    /// Whether the latest run got slower by more than `threshold` percent.
+    /// A stale result wasn't part of the latest run, so it never regressed.
+    pub fn regressed(&self, threshold: f64) -> bool {
+        !self.stale && self.change() > threshold
+    }
+}
+
+/// Compares the latest run against `baseline`, printing the change of every
+/// benchmark. Fails if any of them got slower by more than `threshold` percent.
+///
+/// Benchmarks left out of the latest run, such as by a filtered `cargo bench`,
+/// are still shown, but marked as stale and not held against the threshold.
+pub fn run(baseline: &str, threshold: f64) -> Result<()> {
+    let dir = criterion_dir();
+    let deltas = compare(&dir, baseline)?;
+
+    if deltas.is_empty() {
+        bail!(
+            "No benchmarks to compare against {baseline:?} in {}, save one with `cargo bench -- --save-baseline {baseline}`",
+            dir.display()
+        );
+    }
+
+    let width = deltas.iter().map(|d| d.id.len()).max().unwrap_or(0);
+    for delta in &deltas {
+        println!(
+            "{:width$}  {:>12} -> {:>12}  {:+7.1}%{}",
+            delta.id,
+            format!("{:.2?}", delta.baseline),
+            format!("{:.2?}", delta.current),
+            delta.change(),
+            if delta.stale {
+                "  stale"
+            } else if delta.regressed(threshold) {
+                "  regressed"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let regressed = deltas.iter().filter(|d| d.regressed(threshold)).count();
+    if regressed > 0 {
+        bail!(
+            "{regressed} of {} benchmarks got more than {threshold}% slower than {baseline:?}",
+            deltas.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Finds every benchmark under `dir` that has both a result for `baseline` and
+/// one for the latest run, in the order the days were solved.
+///
+/// Criterion keeps the latest result of every benchmark, whichever run it was
+/// from, so the ones written before the latest run started are marked stale.
+pub fn compare(dir: &Path, baseline: &str) -> Result<Vec<Delta>> {
+    let mut found = Vec::new();
+    collect(dir, baseline, &mut found)?;
+
+    let started = run_start(found.iter().map(|(_, written)| *written));
+    let mut deltas = found
+        .into_iter()
+        .map(|(delta, written)| Delta {
+            stale: started.is_some_and(|started| written < started),
+            ..delta
+        })
+        .collect_vec();
+
+    deltas.sort_by_cached_key(|delta| natural_key(&delta.id));
+
+    Ok(deltas)
+}
+
+/// When the latest run wrote its first result, going back from the newest one
+/// for as long as each was written within [`RUN_GAP`] of the next.
+fn run_start(written: impl Iterator<Item = SystemTime>) -> Option<SystemTime> {
+    written
+        .sorted_by(|a, b| b.cmp(a))
+        .coalesce(|later, earlier| match later.duration_since(earlier) {
+            Ok(gap) if gap <= RUN_GAP => Ok(earlier),
+            _ => Err((later, earlier)),
+        })
+        .next()
+}
+
+fn collect(dir: &Path, baseline: &str, found: &mut Vec<(Delta, SystemTime)>) -> Result<()> {
+    let (new, saved) = (dir.join("new"), dir.join(baseline));
+
+    if new.join("estimates.json").is_file() && saved.join("estimates.json").is_file() {
+        let estimates = new.join("estimates.json");
+        let written = fs::metadata(&estimates)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Failed reading {}", estimates.display()))?;
+
+        let delta = Delta {
+            id: id(&new.join("benchmark.json")).unwrap_or_else(|_| dir.display().to_string()),
+            baseline: mean(&saved.join("estimates.json"))?,
+            current: mean(&estimates)?,
+            stale: false,
+        };
+        found.push((delta, written));
+
+        return Ok(());
+    }
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) if !dir.exists() => return Ok(()),
+        Err(err) => return Err(err).with_context(|| format!("Failed reading {}", dir.display())),
+    };
+
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(&path, baseline, found)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn id(path: &Path) -> Result<String> {
+    let benchmark: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+
+    benchmark["full_id"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| anyhow!("No full_id in {}", path.display()))
+}
+
+fn mean(path: &Path) -> Result<Duration> {
+    let estimates: Value = serde_json::from_str(
+        &fs::read_to_string(path).with_context(|| format!("Failed reading {}", path.display()))?,
+    )?;
+
+    let nanos = estimates["mean"]["point_estimate"]
+        .as_f64()
+        .ok_or_else(|| anyhow!("No mean estimate in {}", path.display()))?;
+
+    Ok(Duration::from_secs_f64(nanos / 1e9))
+}
+
+/// Sorts numbers by their value, so day 2 comes before day 10.
+fn natural_key(id: &str) -> Vec<(String, u64)> {
+    id.chars()
+        .group_by(char::is_ascii_digit)
+        .into_iter()
+        .map(|(digits, chunk)| {
+            let chunk = chunk.collect::<String>();
+
+            if digits {
+                (String::new(), chunk.parse().unwrap_or(u64::MAX))
+            } else {
+                (chunk, 0)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::process;
+
+    use super::*;
+    use test_case::test_case;
+
+    /// Lays out benchmark results the way criterion does, in a directory of
+    /// its own that's removed once the test is done.
+    struct CriterionDir(PathBuf);
+
+    impl CriterionDir {
+        fn new(name: &str) -> Self {
+            let dir = env::temp_dir().join(format!("aoc-bench-{}-{name}", process::id()));
+            let _ = fs::remove_dir_all(&dir);
+
+            Self(dir)
+        }
+
+        fn save(&self, id: &str, run: &str, nanos: f64) -> &Self {
+            let dir = self.0.join(id).join(run);
+            fs::create_dir_all(&dir).unwrap();
+
+            let benchmark = serde_json::json!({ "full_id": id });
+            let estimates = serde_json::json!({ "mean": { "point_estimate": nanos } });
+            fs::write(dir.join("benchmark.json"), benchmark.to_string()).unwrap();
+            fs::write(dir.join("estimates.json"), estimates.to_string()).unwrap();
+
+            self
+        }
+
+        /// Backdates the latest result of a benchmark, as if it was from an
+        /// earlier run.
+        fn written(&self, id: &str, ago: Duration) -> &Self {
+            let estimates = self.0.join(id).join("new").join("estimates.json");
+            fs::File::options()
+                .write(true)
+                .open(estimates)
+                .unwrap()
+                .set_modified(SystemTime::now() - ago)
+                .unwrap();
+
+            self
+        }
+    }
+
+    impl Drop for CriterionDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let dir = CriterionDir::new("compare");
+        dir.save("2022 day 10/part 1", "main", 1000.0)
+            .save("2022 day 10/part 1", "new", 1500.0)
+            .save("2022 day 2/parse", "main", 2000.0)
+            .save("2022 day 2/parse", "new", 1000.0)
+            .save("2022 day 3/parse", "new", 1000.0)
+            .save("2022 day 4/parse", "other", 1000.0)
+            .save("2022 day 4/parse", "new", 1000.0);
+
+        let deltas = compare(&dir.0, "main").unwrap();
+
+        let expected = vec![
+            Delta {
+                id: "2022 day 2/parse".to_string(),
+                baseline: Duration::from_micros(2),
+                current: Duration::from_micros(1),
+                stale: false,
+            },
+            Delta {
+                id: "2022 day 10/part 1".to_string(),
+                baseline: Duration::from_micros(1),
+                current: Duration::from_nanos(1500),
+                stale: false,
+            },
+        ];
+
+        assert_eq!(deltas, expected);
+    }
+
+    #[test]
+    fn test_compare_stale() {
+        let dir = CriterionDir::new("stale");
+        dir.save("2022 day 1/parse", "main", 1000.0)
+            .save("2022 day 1/parse", "new", 2000.0)
+            .save("2022 day 2/parse", "main", 1000.0)
+            .save("2022 day 2/parse", "new", 2000.0)
+            .save("2022 day 3/parse", "main", 1000.0)
+            .save("2022 day 3/parse", "new", 2000.0)
+            .written("2022 day 1/parse", Duration::from_secs(24 * 60 * 60))
+            .written("2022 day 2/parse", Duration::from_secs(60));
+
+        let stale = compare(&dir.0, "main")
+            .unwrap()
+            .into_iter()
+            .map(|delta| (delta.stale, delta.regressed(10.0)))
+            .collect_vec();
+
+        assert_eq!(stale, [(true, false), (false, true), (false, true)]);
+    }
+
+    #[test]
+    fn test_compare_missing_dir() {
+        let dir = CriterionDir::new("missing");
+
+        assert!(compare(&dir.0, "main").unwrap().is_empty());
+    }
+
+    #[test_case(1000, 1500, 50.0, true ; "slower")]
+    #[test_case(1000, 1040, 4.0, false ; "within threshold")]
+    #[test_case(1000, 500, -50.0, false ; "faster")]
+    fn test_delta(baseline: u64, current: u64, change: f64, regressed: bool) {
+        let delta = Delta {
+            id: "2022 day 15/part 2".to_string(),
+            baseline: Duration::from_nanos(baseline),
+            current: Duration::from_nanos(current),
+            stale: false,
+        };
+
+        assert!((delta.change() - change).abs() < 1e-9, "{}", delta.change());
+        assert_eq!(delta.regressed(5.0), regressed);
+    }
+}
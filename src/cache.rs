@@ -0,0 +1,456 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use crate::{params::Resolved, prelude::*, registry::Day, runner::Report};
+
+/// Answers that were already worked out, so slow days don't have to be solved
+/// again when neither their input nor their solver changed.
+///
+/// Every entry is stored under a hash of the input, the solver's source, the
+/// code shared by every solver and the parameters, so an entry left over from
+/// before any of them changed is stale and just gets solved and stored again.
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<String, Entry>,
+    bypassed: bool,
+    changed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    key: String,
+    answers: [Answer; 2],
+}
+
+/// Where the cache is kept unless told otherwise, next to the build output of
+/// the project, wherever it's run from.
+pub fn default_path() -> PathBuf {
+    let target = env::var_os("CARGO_TARGET_DIR").unwrap_or_else(|| "target".into());
+
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(target)
+        .join("aoc-cache.json")
+}
+
+macro_rules! shared {
+    ($($path:literal),* $(,)?) => {
+        &[$(($path, include_str!(concat!("../", $path)))),*]
+    };
+}
+
+/// The code every solver builds on, the code deciding which parameters and
+/// input reach it, and the exact versions of the dependencies, which the
+/// answers depend on as much as the solver's own source does. Each is given by
+/// its path from the root of the project, along with what's in it.
+pub(crate) const SHARED: &[(&str, &str)] = shared![
+    "Cargo.toml",
+    "Cargo.lock",
+    "src/lib.rs",
+    "src/answer.rs",
+    "src/cancel.rs",
+    "src/params.rs",
+    "src/parse.rs",
+    "src/progress.rs",
+    "src/registry.rs",
+    "src/runner.rs",
+    "src/vector.rs",
+];
+
+impl Cache {
+    /// Loads the cache at `path`, starting an empty one if there's none yet.
+    /// A cache that can't be parsed, such as one written by an older version,
+    /// is as good as none.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+
+        Ok(Self {
+            entries: read(&path)?,
+            path,
+            bypassed: false,
+            changed: false,
+        })
+    }
+
+    /// A cache that doesn't hand out any answers, so every day gets solved
+    /// again, while still keeping what they come up with. The cache at `path`
+    /// isn't even read until it's saved to.
+    pub fn bypassed(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            entries: HashMap::new(),
+            bypassed: true,
+            changed: false,
+        }
+    }
+
+    /// The cached answers of both parts, unless the day's input, solver, the
+    /// code it builds on or its parameters changed since they were stored.
+    pub fn get(&self, day: &Day, params: &Resolved) -> Option<&[Answer; 2]> {
+        self.entries
+            .get(&name(day))
+            .filter(|entry| !self.bypassed && entry.key == key(day, params))
+            .map(|entry| &entry.answers)
+    }
+
+    /// Keeps the answers of a report, but only if both parts came up with one.
+    pub fn insert(&mut self, day: &Day, params: &Resolved, report: &Report) {
+        let (Ok(part1), Ok(part2)) = (&report.part1.answer, &report.part2.answer) else {
+            return;
+        };
+
+        let entry = Entry {
+            key: key(day, params),
+            answers: [part1.clone(), part2.clone()],
+        };
+
+        if self.entries.get(&name(day)) != Some(&entry) {
+            self.entries.insert(name(day), entry);
+            self.changed = true;
+        }
+    }
+
+    /// Writes the cache back, if anything was added to it, on top of whatever
+    /// else is in the cache by now.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut entries = read(&self.path)?;
+        entries.extend(self.entries.clone());
+
+        fs::write(&self.path, serialize(&entries))
+            .with_context(|| format!("Failed writing the cache to {}", self.path.display()))?;
+        self.changed = false;
+
+        Ok(())
+    }
+}
+
+/// The entries of the cache at `path`, if there is one that can be parsed.
+fn read(path: &Path) -> Result<HashMap<String, Entry>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(parse(&content).unwrap_or_default()),
+        Err(_) if !path.exists() => Ok(HashMap::new()),
+        Err(err) => Err(err).with_context(|| format!("Failed reading {}", path.display())),
+    }
+}
+
+fn name(day: &Day) -> String {
+    format!("{}/{}", day.year, day.day)
+}
+
+/// Hashes the input, the solver's source, the shared code and the parameters
+/// together.
+fn key(day: &Day, params: &Resolved) -> String {
+    key_with(day, params, SHARED)
+}
+
+fn key_with(day: &Day, params: &Resolved, shared: &[(&str, &str)]) -> String {
+    let hash = [day.input, day.source]
+        .into_iter()
+        .chain(shared.iter().map(|(_, source)| *source))
+        .chain([params.key()])
+        .fold(FNV_OFFSET, |hash, part| {
+            fnv1a(fnv1a(hash, part.as_bytes()), &[0xff])
+        });
+
+    format!("{hash:016x}")
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// FNV-1a, which unlike std's hasher gives the same hash on every build, so
+/// the cache survives upgrading the compiler.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn serialize(entries: &HashMap<String, Entry>) -> String {
+    let entries = entries
+        .iter()
+        .map(|(name, entry)| {
+            let answers = entry.answers.iter().map(answer_to_json).collect_vec();
+
+            (
+                name.clone(),
+                json!({ "key": entry.key, "answers": answers }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+
+    serde_json::to_string_pretty(&entries).unwrap()
+}
+
+fn parse(content: &str) -> Result<HashMap<String, Entry>> {
+    let entries: HashMap<String, Value> = serde_json::from_str(content)?;
+
+    entries
+        .into_iter()
+        .map(|(name, entry)| {
+            let key = entry["key"]
+                .as_str()
+                .ok_or_else(|| anyhow!("No key for {name}"))?
+                .to_string();
+
+            let answers = entry["answers"]
+                .as_array()
+                .ok_or_else(|| anyhow!("No answers for {name}"))?
+                .iter()
+                .map(answer_from_json)
+                .collect::<Result<Vec<_>>>()?
+                .try_into()
+                .map_err(|_| anyhow!("Expected two answers for {name}"))?;
+
+            Ok((name, Entry { key, answers }))
+        })
+        .collect()
+}
+
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Integer(v) => json!({ "integer": v }),
+        Answer::Signed(v) => json!({ "signed": v }),
+        Answer::Text(s) => json!({ "text": s }),
+        Answer::Image(s) => json!({ "image": s }),
+    }
+}
+
+fn answer_from_json(value: &Value) -> Result<Answer> {
+    let err = || anyhow!("Not an answer: {value}");
+
+    let (kind, v) = value
+        .as_object()
+        .and_then(|object| object.iter().next())
+        .ok_or_else(err)?;
+
+    Ok(match kind.as_str() {
+        "integer" => Answer::Integer(v.as_u64().ok_or_else(err)?),
+        "signed" => Answer::Signed(v.as_i64().ok_or_else(err)?),
+        "text" => Answer::Text(v.as_str().ok_or_else(err)?.to_string()),
+        "image" => Answer::Image(v.as_str().ok_or_else(err)?.to_string()),
+        _ => return Err(err()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::{process, time::Duration};
+
+    use super::*;
+    use crate::{
+        params::{self, Override},
+        runner::{Cost, Stage},
+    };
+    use test_case::test_case;
+
+    #[derive(Debug)]
+    struct Rounds(usize);
+
+    impl Default for Rounds {
+        fn default() -> Self {
+            Self(20)
+        }
+    }
+
+    impl params::Params for Rounds {
+        fn set(&mut self, _name: &str, value: &str) -> Result<()> {
+            self.0 = value.parse()?;
+
+            Ok(())
+        }
+    }
+
+    fn day(input: &'static str, source: &'static str) -> Day {
+        Day {
+            year: 2022,
+            day: 1,
+            input,
+            source,
+            parse: |_| Ok(Box::new(())),
+            params: |overrides| Resolved::new::<Rounds>(overrides),
+            part1: |_, _| Ok(1_usize.into()),
+            part2: |_, _| Ok(2_usize.into()),
+            visualise: None,
+        }
+    }
+
+    fn params(rounds: &str) -> Resolved {
+        Resolved::new::<Rounds>(&[Override::new("rounds", rounds)]).unwrap()
+    }
+
+    fn report(part2: Result<Answer>) -> Report {
+        let cost = Cost {
+            elapsed: Duration::ZERO,
+            allocs: None,
+        };
+
+        Report {
+            year: 2022,
+            day: 1,
+            parse: cost,
+            part1: Stage {
+                answer: Ok(Answer::Integer(24000)),
+                cost,
+            },
+            part2: Stage {
+                answer: part2,
+                cost,
+            },
+        }
+    }
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-cache-{}-{name}.json", process::id()))
+    }
+
+    #[test_case(b"", 0xcbf29ce484222325 ; "empty")]
+    #[test_case(b"a", 0xaf63dc4c8601ec8c ; "a")]
+    #[test_case(b"foobar", 0x85944171f73967e8 ; "foobar")]
+    fn test_fnv1a(bytes: &[u8], expected: u64) {
+        assert_eq!(fnv1a(FNV_OFFSET, bytes), expected);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = path("round-trip");
+        let day = day("1000\n2000", "fn part1() {}");
+        let answers = [Answer::Integer(24000), Answer::Image("#.\n.#".to_string())];
+
+        let mut cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get(&day, &params("20")), None);
+
+        cache.insert(&day, &params("20"), &report(Ok(answers[1].clone())));
+        cache.save().unwrap();
+
+        let loaded = Cache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get(&day, &params("20")), Some(&answers));
+    }
+
+    #[test_case("2000\n1000", "fn part1() {}", "20" ; "input")]
+    #[test_case("1000\n2000", "fn part1() { todo!() }", "20" ; "source")]
+    #[test_case("1000\n2000", "fn part1() {}", "10000" ; "params")]
+    fn test_stale(input: &'static str, source: &'static str, rounds: &str) {
+        let mut cache = Cache::load(path("stale")).unwrap();
+
+        cache.insert(
+            &day("1000\n2000", "fn part1() {}"),
+            &params("20"),
+            &report(Ok(45000_usize.into())),
+        );
+
+        assert_eq!(cache.get(&day(input, source), &params(rounds)), None);
+    }
+
+    #[test]
+    fn test_failed_part_is_not_cached() {
+        let day = day("1000", "");
+        let mut cache = Cache::load(path("failed")).unwrap();
+
+        cache.insert(&day, &params("20"), &report(Err(anyhow!("No elves"))));
+
+        assert_eq!(cache.get(&day, &params("20")), None);
+        assert!(!cache.changed);
+    }
+
+    #[test]
+    fn test_bypass() {
+        let day = day("1000", "");
+        let mut cache = Cache::bypassed(path("bypass"));
+
+        cache.insert(&day, &params("20"), &report(Ok(45000_usize.into())));
+
+        assert_eq!(cache.get(&day, &params("20")), None);
+        assert!(cache.changed);
+    }
+
+    #[test]
+    fn test_unparsable_cache_is_empty() {
+        let path = path("unparsable");
+        let day = day("1000", "");
+        fs::write(&path, "[\"from an older version\"]").unwrap();
+
+        let mut cache = Cache::load(&path).unwrap();
+        assert_eq!(cache.get(&day, &params("20")), None);
+
+        cache.insert(&day, &params("20"), &report(Ok(45000_usize.into())));
+        cache.save().unwrap();
+
+        let loaded = Cache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.get(&day, &params("20")).is_some());
+    }
+
+    #[test]
+    fn test_save_keeps_other_entries() {
+        let path = path("keeps");
+        let (first, second) = (
+            day("1000", ""),
+            Day {
+                day: 2,
+                ..day("A Y", "")
+            },
+        );
+
+        let mut cache = Cache::load(&path).unwrap();
+        cache.insert(&first, &params("20"), &report(Ok(45000_usize.into())));
+        cache.save().unwrap();
+
+        let mut cache = Cache::bypassed(&path);
+        cache.insert(&second, &params("20"), &report(Ok(15_usize.into())));
+        cache.save().unwrap();
+
+        let loaded = Cache::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.get(&first, &params("20")).is_some());
+        assert!(loaded.get(&second, &params("20")).is_some());
+    }
+
+    #[test]
+    fn test_default_path_is_absolute() {
+        assert!(default_path().is_absolute());
+    }
+
+    #[test]
+    fn test_stale_shared_code() {
+        let day = day("1000\n2000", "fn part1() {}");
+
+        assert_eq!(
+            key_with(&day, &params("20"), SHARED),
+            key(&day, &params("20"))
+        );
+        assert_ne!(
+            key_with(
+                &day,
+                &params("20"),
+                &[("src/vector.rs", "pub struct Vector2(isize, isize);")]
+            ),
+            key(&day, &params("20"))
+        );
+    }
+
+    #[test_case(Answer::Integer(24000))]
+    #[test_case(Answer::Signed(-3))]
+    #[test_case(Answer::Text("CMZ".to_string()))]
+    #[test_case(Answer::Image("#.\n.#".to_string()))]
+    fn test_answer_json(answer: Answer) {
+        assert_eq!(answer_from_json(&answer_to_json(&answer)).unwrap(), answer);
+    }
+}
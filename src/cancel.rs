@@ -0,0 +1,182 @@
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering::Relaxed},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::prelude::*;
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// The error a task stops with once it's been cancelled. It displays as
+/// `TIMEOUT`, since running out of time is what cancels tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "TIMEOUT")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+/// Tells a running task to stop, either when it's cancelled by hand or once
+/// its deadline passes. Clones share the same state.
+///
+/// Tasks stop cooperatively: long-running solvers call [`check`] every so
+/// often and return early with [`Cancelled`]. That's days 11, 12, 14, 15 and
+/// 16 of 2022; every other day finishes in well under a second and runs to
+/// the end whatever its deadline.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Instant::now().checked_add(timeout),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Relaxed) {
+            return true;
+        }
+
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            self.cancel();
+            return true;
+        }
+
+        false
+    }
+
+    /// Fails with [`Cancelled`] once the token is cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            return Err(Cancelled.into());
+        }
+
+        Ok(())
+    }
+
+    /// Runs `f` with this as the current token of the thread, so whatever it
+    /// calls can [`check`] it.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+
+        f()
+    }
+}
+
+/// Puts the previous token back once a scope ends, even if it panicked.
+struct Restore(Option<CancellationToken>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(self.0.take()));
+    }
+}
+
+/// The token of the task running on this thread, which never gets cancelled
+/// if there's none. Work handed to other threads, such as rayon's, has to take
+/// it along and check it there.
+pub fn current() -> CancellationToken {
+    CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Fails with [`Cancelled`] once the task running on this thread is cancelled.
+pub fn check() -> Result<()> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(token) => token.check(),
+        None => Ok(()),
+    })
+}
+
+/// Whether a task failed because it was cancelled, even if context was added
+/// to the error on its way up.
+pub fn is_cancelled(err: &Error) -> bool {
+    err.downcast_ref::<Cancelled>().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_check_outside_scope() {
+        assert!(check().is_ok());
+        assert!(!current().is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel() {
+        let token = CancellationToken::new();
+
+        token.scope(|| {
+            assert!(check().is_ok());
+
+            current().cancel();
+
+            let err = check().unwrap_err();
+            assert!(is_cancelled(&err));
+            assert_eq!(err.to_string(), "TIMEOUT");
+        });
+
+        assert!(token.is_cancelled());
+        assert!(check().is_ok());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let token = CancellationToken::with_timeout(Duration::ZERO);
+
+        assert!(token.scope(check).is_err());
+        assert!(CancellationToken::with_timeout(Duration::from_secs(60))
+            .scope(check)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_is_cancelled_with_context() {
+        let err = Err::<(), _>(Error::from(Cancelled))
+            .context("Failed parsing 2022 day 16")
+            .unwrap_err();
+
+        assert!(is_cancelled(&err));
+        assert!(!is_cancelled(&anyhow!("No valves")));
+    }
+
+    #[test]
+    fn test_nested_scopes() {
+        let outer = CancellationToken::new();
+        let inner = CancellationToken::with_timeout(Duration::ZERO);
+
+        outer.scope(|| {
+            assert!(inner.scope(check).is_err());
+            assert!(check().is_ok());
+        });
+    }
+}
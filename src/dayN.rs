@@ -1,22 +1,20 @@
-// Template
+// Template, copy into the year's module as `dayN.rs` and register it in the
+// year's `YEAR` and in Cargo.toml's features. Once the answers are accepted,
+// add them to the year's `answers.json`.
 
 use crate::prelude::*;
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/dayN.txt");
+pub const INPUT: &str = include_str!("../../input/YYYY/dayN.txt");
 
-    println!("{}", task1(input)?);
-
-    println!("{}", task2(input)?);
-
-    Ok(())
+pub fn parse(input: &str) -> Result<()> {
+    todo!()
 }
 
-fn task1(input: &str) -> Result<usize> {
+pub fn part1(model: &()) -> Result<Answer> {
     todo!()
 }
 
-fn task2(input: &str) -> Result<usize> {
+pub fn part2(model: &()) -> Result<Answer> {
     todo!()
 }
 
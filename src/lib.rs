@@ -1,23 +1,25 @@
-pub mod day1;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-
+pub mod alloc;
+pub mod answer;
+pub mod bench;
+pub mod cache;
+pub mod cancel;
+pub mod params;
+pub mod parse;
+pub mod progress;
+pub mod registry;
+pub mod report;
+pub mod runner;
+#[cfg(test)]
+#[allow(dead_code)] // when building without any days
+mod snapshot;
 pub mod vector;
+pub mod visual;
+pub mod watch;
+pub mod y2022;
 
 pub mod prelude {
     pub use anyhow::{anyhow, bail, Context, Error, Result};
     pub use itertools::Itertools;
+
+    pub use crate::{answer::Answer, scan};
 }
@@ -1,5 +1,134 @@
-use aoc::{prelude::*, *};
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use aoc::{cache::Cache, params::Override, prelude::*, report::Format, visual::Headless, *};
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: alloc::CountingAllocator = alloc::CountingAllocator;
+
+const USAGE: &str = "usage:
+    aoc [run [YEAR DAY]] [--param NAME=VALUE]... [--no-cache] [--timeout SECONDS]
+    aoc run YEAR [--no-cache] [--timeout SECONDS]
+    aoc run YEAR DAY --input PATH [--param NAME=VALUE]... [--json] [--timeout SECONDS]
+    aoc watch [YEAR] DAY
+    aoc report [--format markdown|html] [--output PATH] [--timeout SECONDS]
+    aoc bench compare BASELINE [--threshold PERCENT]
+    aoc visualise YEAR DAY [PART] [--param NAME=VALUE]... [--headless [--every STEPS] [--frames FRAMES]]";
 
 fn main() -> Result<()> {
-    day15::run()
+    let mut args = env::args().skip(1).collect_vec();
+
+    let no_cache = take_flag(&mut args, "--no-cache");
+    let timeout = take_option::<f64>(&mut args, "--timeout")?
+        .map(Duration::try_from_secs_f64)
+        .transpose()?;
+    let json = take_flag(&mut args, "--json");
+    let input = take_option::<PathBuf>(&mut args, "--input")?;
+    let headless = take_flag(&mut args, "--headless");
+    let every = take_option(&mut args, "--every")?;
+    let frames = take_option(&mut args, "--frames")?;
+    let format = take_option(&mut args, "--format")?;
+    let output = take_option::<PathBuf>(&mut args, "--output")?;
+    let threshold = take_option(&mut args, "--threshold")?;
+    let params = take_options::<Override>(&mut args, "--param")?;
+
+    match args.iter().map(String::as_str).collect_vec().as_slice() {
+        [] | ["run"] => runner::run(registry::latest()?, &params, &mut cache(no_cache)?, timeout),
+        ["run", year] => {
+            // every day has parameters of its own, so they can only be given
+            // for a single day
+            if !params.is_empty() {
+                bail!("--param needs a single day to run, as in `aoc run {year} DAY --param NAME=VALUE`");
+            }
+
+            let mut cache = cache(no_cache)?;
+
+            for day in registry::year(year.parse()?)?.days {
+                runner::run(day, &params, &mut cache, timeout)?;
+                println!();
+            }
+
+            Ok(())
+        }
+        ["run", year, day] => {
+            let day = registry::get(year.parse()?, day.parse()?)?;
+
+            match &input {
+                Some(path) => {
+                    let input = fs::read_to_string(path)
+                        .with_context(|| format!("Failed reading {}", path.display()))?;
+
+                    runner::run_on(day, &input, &params, json, timeout)
+                }
+                None => runner::run(day, &params, &mut cache(no_cache)?, timeout),
+            }
+        }
+        ["visualise", year, day, part @ ..] if part.len() <= 1 => {
+            let day = registry::get(year.parse()?, day.parse()?)?;
+            let part = part.first().map_or(Ok(1), |part| part.parse())?;
+            let headless = headless.then(|| Headless {
+                every: every.unwrap_or(1),
+                frames,
+            });
+
+            visual::run(day, part, &params, headless)
+        }
+        ["report"] => {
+            let format = format
+                .or_else(|| output.as_deref().and_then(Format::from_path))
+                .unwrap_or(Format::Markdown);
+
+            report::run(format, output.as_deref(), timeout)
+        }
+        ["watch", day] => watch::run(registry::latest()?.year, day.parse()?),
+        ["watch", year, day] => watch::run(year.parse()?, day.parse()?),
+        ["bench", "compare", baseline] => bench::run(baseline, threshold.unwrap_or(10.0)),
+        _ => bail!(USAGE),
+    }
+}
+
+fn cache(no_cache: bool) -> Result<Cache> {
+    match no_cache {
+        true => Ok(Cache::bypassed(cache::default_path())),
+        false => Cache::load(cache::default_path()),
+    }
+}
+
+/// Removes `flag` from the arguments, returning whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len = args.len();
+    args.retain(|arg| arg != flag);
+
+    args.len() != len
+}
+
+/// Removes `option` and the value following it from the arguments, and parses
+/// the value.
+fn take_option<T>(args: &mut Vec<String>, option: &str) -> Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    let Some(idx) = args.iter().position(|arg| arg == option) else {
+        return Ok(None);
+    };
+
+    let value = args
+        .get(idx + 1)
+        .ok_or_else(|| anyhow!("{option} needs a value"))?
+        .parse()
+        .map_err(|err| anyhow!("Invalid value for {option}: {err}"))?;
+    args.drain(idx..=idx + 1);
+
+    Ok(Some(value))
+}
+
+/// Removes every occurrence of `option` and the values following them from the
+/// arguments, and parses the values in the order they were given.
+fn take_options<T>(args: &mut Vec<String>, option: &str) -> Result<Vec<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    std::iter::from_fn(|| take_option(args, option).transpose()).collect()
 }
@@ -0,0 +1,192 @@
+use std::{any::Any, fmt::Debug, fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use crate::prelude::*;
+
+/// A day's puzzle parameters, such as the row to look at or how many rounds to
+/// play. They default to what the puzzle asks for, and can be overridden by
+/// name, which is how the examples get their own.
+pub trait Params: Default + Debug + Send + Sync + 'static {
+    /// Sets the parameter called `name` from the way it was written.
+    fn set(&mut self, name: &str, value: &str) -> Result<()>;
+}
+
+/// Days that take no parameters.
+impl Params for () {
+    fn set(&mut self, name: &str, _value: &str) -> Result<()> {
+        bail!("There is no parameter called {name}")
+    }
+}
+
+/// A parameter given by name, written as `NAME=VALUE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Override {
+    pub name: String,
+    pub value: String,
+}
+
+impl Override {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl FromStr for Override {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, value) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow!("Expected NAME=VALUE, got {s:?}"))?;
+
+        Ok(Self::new(name.trim(), value.trim()))
+    }
+}
+
+impl Display for Override {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+/// A day's parameters with their type erased, like its model, so every day can
+/// be handed them the same way.
+pub struct Resolved {
+    params: Box<dyn Any + Send + Sync>,
+    key: String,
+}
+
+impl Resolved {
+    /// The puzzle's own parameters, with `overrides` applied in order.
+    pub fn new<P: Params>(overrides: &[Override]) -> Result<Self> {
+        let mut params = P::default();
+
+        for o in overrides {
+            params
+                .set(&o.name, &o.value)
+                .with_context(|| format!("Failed setting {o}"))?;
+        }
+
+        Ok(Self {
+            key: format!("{params:?}"),
+            params: Box::new(params),
+        })
+    }
+
+    pub fn get<P: 'static>(&self) -> Result<&P> {
+        self.params
+            .downcast_ref::<P>()
+            .ok_or_else(|| anyhow!("Params are not a {}", std::any::type_name::<P>()))
+    }
+
+    /// Tells apart different values of the parameters, so answers to one set
+    /// aren't mistaken for answers to another.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+/// Parses a range written the way Rust writes it, such as `0..=20`.
+pub fn range<T>(value: &str) -> Result<RangeInclusive<T>>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let (start, end) = value
+        .split_once("..=")
+        .ok_or_else(|| anyhow!("Expected START..=END, got {value:?}"))?;
+
+    Ok(start.trim().parse()?..=end.trim().parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Beacons {
+        row: isize,
+        range: RangeInclusive<isize>,
+    }
+
+    impl Default for Beacons {
+        fn default() -> Self {
+            Self {
+                row: 2000000,
+                range: 1..=4000000,
+            }
+        }
+    }
+
+    impl Params for Beacons {
+        fn set(&mut self, name: &str, value: &str) -> Result<()> {
+            match name {
+                "row" => self.row = value.parse()?,
+                "range" => self.range = range(value)?,
+                _ => bail!("There is no parameter called {name}"),
+            }
+
+            Ok(())
+        }
+    }
+
+    fn overrides(overrides: &[&str]) -> Vec<Override> {
+        overrides.iter().map(|o| o.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let resolved = Resolved::new::<Beacons>(&[]).unwrap();
+
+        assert_eq!(resolved.get::<Beacons>().unwrap(), &Beacons::default());
+        assert!(resolved.get::<()>().is_err());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let resolved = Resolved::new::<Beacons>(&overrides(&["row=10", "range = 0..=20"])).unwrap();
+
+        let expected = Beacons {
+            row: 10,
+            range: 0..=20,
+        };
+        assert_eq!(resolved.get::<Beacons>().unwrap(), &expected);
+    }
+
+    #[test_case(&["row=ten"], "Failed setting row=ten" ; "invalid value")]
+    #[test_case(&["column=10"], "Failed setting column=10" ; "unknown")]
+    #[test_case(&["range=0..20"], "Failed setting range=0..20" ; "invalid range")]
+    fn test_invalid_overrides(invalid: &[&str], expected: &str) {
+        let err = Resolved::new::<Beacons>(&overrides(invalid)).err().unwrap();
+
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_no_params() {
+        assert!(Resolved::new::<()>(&[]).is_ok());
+        assert!(Resolved::new::<()>(&overrides(&["row=10"])).is_err());
+    }
+
+    #[test]
+    fn test_key() {
+        let key = |o: &[&str]| {
+            Resolved::new::<Beacons>(&overrides(o))
+                .unwrap()
+                .key()
+                .to_string()
+        };
+
+        assert_eq!(key(&[]), key(&["row=2000000"]));
+        assert_ne!(key(&[]), key(&["row=10"]));
+    }
+
+    #[test]
+    fn test_override_without_value() {
+        assert!("row".parse::<Override>().is_err());
+    }
+}
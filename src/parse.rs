@@ -0,0 +1,249 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::prelude::*;
+
+/// Matches a line against a template and parses every `{}` placeholder into
+/// the given type, returning them as a tuple.
+///
+/// ```
+/// use aoc::prelude::*;
+///
+/// let (x, y) = scan!("x=2, y=-18", "x={}, y={}", isize, isize)?;
+/// assert_eq!((x, y), (2, -18));
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// See [`captures`](crate::parse::captures) for the template syntax.
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $template:expr, $($ty:ty),+ $(,)?) => {
+        $crate::parse::captures($input, $template).and_then(
+            |captures| -> $crate::prelude::Result<_> {
+                let expected = [$(stringify!($ty)),+].len();
+                if captures.len() != expected {
+                    $crate::prelude::bail!(
+                        "Template {:?} has {} placeholders, but {} types were given",
+                        $template,
+                        captures.len(),
+                        expected
+                    );
+                }
+
+                let mut captures = captures.into_iter();
+
+                Ok(($($crate::parse::field::<$ty>(captures.next().unwrap())?,)+))
+            },
+        )
+    };
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'t> {
+    Literal(&'t str),
+    Optional(&'t str),
+    Capture,
+}
+
+/// Matches `input` against `template` and returns the text captured by every
+/// `{}` placeholder.
+///
+/// Everything else in the template has to match exactly, except `{text?}`,
+/// which matches `text` if it's there and nothing if it isn't. That covers the
+/// singular/plural wording in lines like `tunnel{s?} lead{s?} to valve{s?} {}`.
+///
+/// A placeholder captures everything up to the next literal, so it has to be
+/// followed by one, or be at the very end of the template.
+pub fn captures<'a>(input: &'a str, template: &str) -> Result<Vec<&'a str>> {
+    let tokens = tokenize(template)?;
+
+    let mut rest = input;
+    let mut captures = Vec::new();
+
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Literal(literal) => {
+                rest = rest
+                    .strip_prefix(literal)
+                    .ok_or_else(|| anyhow!("Expected {literal:?} at {rest:?} in {input:?}"))?;
+            }
+            Token::Optional(literal) => rest = rest.strip_prefix(literal).unwrap_or(rest),
+            Token::Capture => {
+                let end = match tokens.get(idx + 1) {
+                    None => rest.len(),
+                    Some(Token::Literal(literal)) => rest.find(literal).ok_or_else(|| {
+                        anyhow!("Expected {literal:?} after {rest:?} in {input:?}")
+                    })?,
+                    Some(_) => bail!("Placeholder must be followed by literal text: {template}"),
+                };
+
+                captures.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        bail!("Unexpected trailing {rest:?} in {input:?}");
+    }
+
+    Ok(captures)
+}
+
+/// Parses a single captured field, keeping the offending text in the error.
+pub fn field<T>(capture: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    capture
+        .parse::<T>()
+        .map_err(|err| anyhow!("Failed parsing {capture:?}: {err}"))
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        if start > 0 {
+            tokens.push(Token::Literal(&rest[..start]));
+        }
+
+        let end = rest[start..]
+            .find('}')
+            .map(|len| start + len)
+            .ok_or_else(|| anyhow!("Unclosed placeholder in template: {template}"))?;
+
+        let placeholder = &rest[start + 1..end];
+        match placeholder.strip_suffix('?') {
+            _ if placeholder.is_empty() => tokens.push(Token::Capture),
+            Some(optional) if !optional.is_empty() => tokens.push(Token::Optional(optional)),
+            _ => bail!("Unknown placeholder {{{placeholder}}} in template: {template}"),
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Literal(rest));
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    const SENSOR: &str = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+    const VALVE: &str = "Valve {} has flow rate={}; tunnel{s?} lead{s?} to valve{s?} {}";
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("x={}, tunnel{s?}").unwrap();
+
+        let expected = vec![
+            Token::Literal("x="),
+            Token::Capture,
+            Token::Literal(", tunnel"),
+            Token::Optional("s"),
+        ];
+
+        assert_eq!(tokens, expected);
+    }
+
+    #[test_case("{" ; "unclosed")]
+    #[test_case("{x}" ; "unknown")]
+    #[test_case("{?}" ; "empty optional")]
+    #[test_case("{}{s?}" ; "capture before optional")]
+    fn test_bad_template(template: &str) {
+        assert!(captures("anything", template).is_err());
+    }
+
+    #[test_case("2-4,6-8", "{}-{},{}-{}", &["2", "4", "6", "8"] ; "day 4 sections")]
+    #[test_case("move 1 from 2 to 1", "move {} from {} to {}", &["1", "2", "1"] ; "day 5 instruction")]
+    #[test_case("R 4", "{} {}", &["R", "4"] ; "day 9 instruction")]
+    #[test_case("addx -5", "addx {}", &["-5"] ; "day 10 signal")]
+    #[test_case("  Test: divisible by 23", "  Test: divisible by {}", &["23"] ; "day 11 test")]
+    #[test_case("498,4", "{},{}", &["498", "4"] ; "day 14 point")]
+    #[test_case("Sensor at x=2, y=18: closest beacon is at x=-2, y=15", SENSOR, &["2", "18", "-2", "15"] ; "day 15 sensor")]
+    #[test_case("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB", VALVE, &["AA", "0", "DD, II, BB"] ; "day 16 valves")]
+    #[test_case("Valve HH has flow rate=22; tunnel leads to valve GG", VALVE, &["HH", "22", "GG"] ; "day 16 valve")]
+    fn test_captures(input: &str, template: &str, expected: &[&str]) {
+        assert_eq!(captures(input, template).unwrap(), expected);
+    }
+
+    #[test_case("Sensor at x=2, y=18" ; "too short")]
+    #[test_case("Sensor at x=2, y=18: closest beacon is at x=-2, y=15 " ; "trailing")]
+    #[test_case("Beacon at x=2, y=18: closest beacon is at x=-2, y=15" ; "wrong prefix")]
+    fn test_scan_mismatch(input: &str) {
+        assert!(scan!(input, SENSOR, isize, isize, isize, isize).is_err());
+    }
+
+    #[test]
+    fn test_scan_typed() {
+        let (name, rate, tunnels) = scan!(
+            "Valve HH has flow rate=22; tunnel leads to valve GG",
+            VALVE,
+            String,
+            isize,
+            String
+        )
+        .unwrap();
+
+        assert_eq!(name, "HH");
+        assert_eq!(rate, 22);
+        assert_eq!(tunnels, "GG");
+    }
+
+    #[test_case("x=1!?", "x={}!" ; "trailing literal")]
+    #[test_case("x=1", "x={}!" ; "missing literal")]
+    fn test_captures_mismatch(input: &str, template: &str) {
+        assert!(captures(input, template).is_err());
+    }
+
+    #[test]
+    fn test_scan_bad_value() {
+        let res = scan!("x=2, y=up", "x={}, y={}", isize, isize);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_scan_wrong_arity() {
+        let res = scan!("x=2, y=3", "x={}, y={}", isize);
+
+        assert!(res.is_err());
+    }
+
+    #[cfg(feature = "y2022-day15")]
+    #[test]
+    fn test_scan_sensor_inputs() {
+        let inputs = [
+            include_str!("../input/2022/day15.txt"),
+            include_str!("../input/2022/day15_example.txt"),
+        ];
+
+        for line in inputs.iter().flat_map(|input| input.lines()) {
+            assert!(
+                scan!(line, SENSOR, isize, isize, isize, isize).is_ok(),
+                "{line}"
+            );
+        }
+    }
+
+    #[cfg(feature = "y2022-day16")]
+    #[test]
+    fn test_scan_valve_inputs() {
+        let inputs = [
+            include_str!("../input/2022/day16.txt"),
+            include_str!("../input/2022/day16_example.txt"),
+        ];
+
+        for line in inputs.iter().flat_map(|input| input.lines()) {
+            assert!(scan!(line, VALVE, String, isize, String).is_ok(), "{line}");
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    cell::RefCell,
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering::Relaxed},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::alloc;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Progress>> = const { RefCell::new(None) };
+}
+
+/// Fast tasks finish before they'd be worth reporting on.
+const QUIET_FOR: Duration = Duration::from_millis(500);
+/// How often the bar is redrawn on a terminal.
+const REDRAW: Duration = Duration::from_millis(100);
+/// How often a line is logged when stderr isn't a terminal.
+const LOG: Duration = Duration::from_secs(5);
+
+const BAR_WIDTH: usize = 30;
+
+/// How far along a task is, shared between the solver counting its work and
+/// the runner drawing it. Clones share the same counters.
+///
+/// Counting is a relaxed atomic add, but hot loops should still count in
+/// batches rather than one at a time, especially across rayon's threads.
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    done: Arc<AtomicU64>,
+    total: Arc<AtomicU64>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts over, with `total` units of work to go.
+    pub fn start(&self, total: u64) {
+        self.done.store(0, Relaxed);
+        self.total.store(total, Relaxed);
+    }
+
+    pub fn inc(&self, units: u64) {
+        self.done.fetch_add(units, Relaxed);
+    }
+
+    /// How many units are done and how many there are in total, if the task
+    /// said how many there are.
+    pub fn get(&self) -> Option<(u64, u64)> {
+        match self.total.load(Relaxed) {
+            0 => None,
+            total => Some((self.done.load(Relaxed).min(total), total)),
+        }
+    }
+
+    /// Runs `f` with this as the current progress of the thread, so whatever
+    /// it calls can report to it.
+    pub fn scope<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+
+        f()
+    }
+}
+
+/// Puts the previous progress back once a scope ends, even if it panicked.
+struct Restore(Option<Progress>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.replace(self.0.take()));
+    }
+}
+
+/// Starts reporting on the task running on this thread, with `total` units of
+/// work to go. Nothing's drawn if nobody's watching the task, so solvers can
+/// always report. Work handed to other threads, such as rayon's, has to take
+/// the returned progress along.
+pub fn start(total: u64) -> Progress {
+    let progress = CURRENT.with(|current| current.borrow().clone().unwrap_or_default());
+    progress.start(total);
+
+    progress
+}
+
+/// Runs `f` as a task called `label`, drawing its progress on stderr once it's
+/// been running for a while: as a bar on a terminal, or as a line every few
+/// seconds otherwise.
+///
+/// Drawing happens on a thread of its own, whose allocations aren't counted,
+/// so they don't show up in what the task is measured to allocate. The task
+/// only starts once that thread is up and left out of the counts.
+pub fn watch<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let progress = Progress::new();
+    let terminal = io::stderr().is_terminal();
+    let (finished, wait) = mpsc::channel::<()>();
+    let (started, ready) = mpsc::sync_channel::<()>(1);
+
+    thread::scope(|s| {
+        let drawn = s.spawn(|| {
+            alloc::uncounted(|| {
+                let _ = started.send(());
+                draw_until_finished(label, &progress, terminal, wait)
+            })
+        });
+        let _ = ready.recv();
+
+        let res = progress.scope(f);
+        drop(finished);
+
+        if drawn.join().unwrap_or(false) && terminal {
+            // clear the bar, so the report takes its place
+            let _ = write!(io::stderr(), "\r\x1b[K");
+        }
+
+        res
+    })
+}
+
+/// Returns whether anything was drawn.
+fn draw_until_finished(
+    label: &str,
+    progress: &Progress,
+    terminal: bool,
+    wait: mpsc::Receiver<()>,
+) -> bool {
+    let started = Instant::now();
+    let interval = if terminal { REDRAW } else { LOG };
+    let mut drawn = false;
+
+    let mut timeout = QUIET_FOR;
+    while let Err(RecvTimeoutError::Timeout) = wait.recv_timeout(timeout) {
+        timeout = interval;
+
+        let Some((done, total)) = progress.get() else {
+            continue;
+        };
+
+        let mut err = io::stderr().lock();
+        let res = if terminal {
+            write!(err, "\r").and_then(|_| draw(&mut err, label, done, total, started.elapsed()))
+        } else {
+            draw(&mut err, label, done, total, started.elapsed()).and_then(|_| writeln!(err))
+        };
+        drawn |= res.is_ok();
+    }
+
+    drawn
+}
+
+/// Draws a single line, such as
+/// `2022 day 15 part 2 [#######.......] 50% (2000000/4000000) 1.2s`.
+fn draw(
+    out: &mut impl Write,
+    label: &str,
+    done: u64,
+    total: u64,
+    elapsed: Duration,
+) -> io::Result<()> {
+    let filled = (done as u128 * BAR_WIDTH as u128 / total as u128) as usize;
+
+    write!(out, "{label} [")?;
+    for idx in 0..BAR_WIDTH {
+        out.write_all(if idx < filled { b"#" } else { b"." })?;
+    }
+    write!(
+        out,
+        "] {}% ({done}/{total}) {:.1?}",
+        done * 100 / total,
+        elapsed
+    )?;
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress::new();
+        assert_eq!(progress.get(), None);
+
+        progress.start(10);
+        progress.inc(4);
+        assert_eq!(progress.get(), Some((4, 10)));
+
+        progress.inc(100);
+        assert_eq!(progress.get(), Some((10, 10)));
+
+        progress.start(20);
+        assert_eq!(progress.get(), Some((0, 20)));
+    }
+
+    #[test]
+    fn test_start_reports_to_the_scope() {
+        let progress = Progress::new();
+
+        progress.scope(|| start(5).inc(2));
+        start(5).inc(3);
+
+        assert_eq!(progress.get(), Some((2, 5)));
+    }
+
+    #[test]
+    fn test_watch_returns_the_result() {
+        watch("2022 day 11 part 2", || start(3).inc(1));
+        assert_eq!(watch("2022 day 1 part 1", || 24000), 24000);
+    }
+
+    #[test_case(0, 4, "[..............................] 0% (0/4)" ; "none")]
+    #[test_case(2, 4, "[###############...............] 50% (2/4)" ; "half")]
+    #[test_case(4, 4, "[##############################] 100% (4/4)" ; "all")]
+    fn test_draw(done: u64, total: u64, expected: &str) {
+        let mut out = Vec::new();
+
+        draw(
+            &mut out,
+            "2022 day 15 part 2",
+            done,
+            total,
+            Duration::from_millis(1200),
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("2022 day 15 part 2 {expected} 1.2s")
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::{any::Any, collections::HashMap, fmt::Display};
+
+use crate::{
+    params::{Override, Resolved},
+    prelude::*,
+    visual::Visualise,
+};
+
+/// A day's parsed input, with its concrete type erased so every day can sit in
+/// the same registry.
+pub type Model = Box<dyn Any + Send + Sync>;
+
+/// Everything needed to solve a single day, without knowing its model type.
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    pub input: &'static str,
+    /// The solver's own source, so cached answers can tell when it changed.
+    pub source: &'static str,
+    pub parse: fn(&str) -> Result<Model>,
+    /// Resolves the day's parameters, the puzzle's own unless overridden.
+    pub params: fn(&[Override]) -> Result<Resolved>,
+    pub part1: fn(&Model, &Resolved) -> Result<Answer>,
+    pub part2: fn(&Model, &Resolved) -> Result<Answer>,
+    /// Steps through one of the parts frame by frame, for days that simulate
+    /// something worth watching.
+    pub visualise: Option<Visualise>,
+}
+
+/// All the days solved for a single event.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+    /// The accepted answers, as JSON mapping each day to its parts' answers.
+    pub answers: &'static str,
+    /// The manifest of the examples, as JSON mapping the name of each example
+    /// input to the parameters it needs and the answers it gives.
+    pub examples: &'static str,
+}
+
+/// The accepted answers of every part of every day, as they're displayed.
+pub type Answers = HashMap<u8, Vec<String>>;
+
+/// An example input's entry in the manifest, such as day 15's example looking
+/// at row 10 rather than row 2000000.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    pub params: Vec<Override>,
+    /// The answers of the parts, as they're displayed.
+    pub answers: Vec<String>,
+}
+
+/// Every example in the manifest, by the name of its input without extension.
+pub type Examples = HashMap<String, Example>;
+
+impl Year {
+    pub fn answers(&self) -> Result<Answers> {
+        let answers: HashMap<String, Vec<String>> = serde_json::from_str(self.answers)
+            .with_context(|| format!("Failed parsing the answers of {}", self.year))?;
+
+        answers
+            .into_iter()
+            .map(|(day, parts)| Ok((day.parse()?, parts)))
+            .collect()
+    }
+
+    pub fn examples(&self) -> Result<Examples> {
+        let examples: HashMap<String, serde_json::Value> = serde_json::from_str(self.examples)
+            .with_context(|| format!("Failed parsing the examples of {}", self.year))?;
+
+        examples
+            .into_iter()
+            .map(|(name, example)| {
+                let params = example["params"]
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .map(|(param, value)| {
+                        // numbers don't need to be quoted
+                        let value = value
+                            .as_str()
+                            .map_or_else(|| value.to_string(), str::to_string);
+
+                        Override::new(param, value)
+                    })
+                    .collect();
+
+                let answers = example["answers"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|answer| {
+                        answer
+                            .as_str()
+                            .map(str::to_string)
+                            .ok_or_else(|| anyhow!("Not an answer of {name}: {answer}"))
+                    })
+                    .collect::<Result<_>>()?;
+
+                Ok((name, Example { params, answers }))
+            })
+            .collect()
+    }
+}
+
+pub const YEARS: &[Year] = &[crate::y2022::YEAR];
+
+/// Builds a [`Day`] out of a module exposing `INPUT`, `parse`, `part1` and
+/// `part2`. Days registered with `params` also expose a `Params` type, which
+/// both parts take as well, and days registered with `visualise` expose
+/// `simulation`. The module is resolved where the macro is used, so each year
+/// can register its own days.
+#[allow(unused_macros)] // when building without any days
+macro_rules! day {
+    ($year:literal, $day:literal, $module:ident) => {
+        $crate::registry::day!(
+            @solve $year, $day, $module, (),
+            |model, _: &()| $module::part1(model),
+            |model, _: &()| $module::part2(model)
+        )
+    };
+    ($year:literal, $day:literal, $module:ident, visualise) => {
+        $crate::registry::Day {
+            visualise: Some(|model, _, part| {
+                $module::simulation($crate::registry::downcast(model, $module::parse)?, part)
+            }),
+            ..$crate::registry::day!($year, $day, $module)
+        }
+    };
+    ($year:literal, $day:literal, $module:ident, params) => {
+        $crate::registry::day!(
+            @solve $year, $day, $module, $module::Params,
+            |model, params| $module::part1(model, params),
+            |model, params| $module::part2(model, params)
+        )
+    };
+    ($year:literal, $day:literal, $module:ident, params, visualise) => {
+        $crate::registry::Day {
+            visualise: Some(|model, params, part| {
+                $module::simulation(
+                    $crate::registry::downcast(model, $module::parse)?,
+                    params.get()?,
+                    part,
+                )
+            }),
+            ..$crate::registry::day!($year, $day, $module, params)
+        }
+    };
+    (@solve $year:literal, $day:literal, $module:ident, $params:ty, $part1:expr, $part2:expr) => {
+        $crate::registry::Day {
+            year: $year,
+            day: $day,
+            input: $module::INPUT,
+            source: include_str!(concat!(stringify!($module), ".rs")),
+            parse: |input| Ok(Box::new($module::parse(input)?)),
+            params: |overrides| $crate::params::Resolved::new::<$params>(overrides),
+            part1: |model, params| $crate::registry::solve(model, params, $module::parse, $part1),
+            part2: |model, params| $crate::registry::solve(model, params, $module::parse, $part2),
+            visualise: None,
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use day;
+
+/// Every registered day, oldest first.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|year| year.days.iter())
+}
+
+pub fn year(year: u16) -> Result<&'static Year> {
+    YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .ok_or_else(|| anyhow!("Year {year} is not registered"))
+}
+
+pub fn get(year: u16, day: u8) -> Result<&'static Day> {
+    self::year(year)?
+        .days
+        .iter()
+        .find(|d| d.day == day)
+        .ok_or_else(|| anyhow!("Day {day} of {year} is not registered"))
+}
+
+pub fn latest() -> Result<&'static Day> {
+    days().last().ok_or_else(|| anyhow!("No days registered"))
+}
+
+/// Gets the model back out of the box. The parser is only there so the
+/// compiler can work out which type to downcast to.
+#[allow(dead_code)] // when building without any days
+pub(crate) fn downcast<M: 'static>(model: &Model, _parse: fn(&str) -> Result<M>) -> Result<&M> {
+    model
+        .downcast_ref::<M>()
+        .ok_or_else(|| anyhow!("Model is not a {}", std::any::type_name::<M>()))
+}
+
+/// Solves a part with the model and the parameters it was written for.
+#[allow(dead_code)] // when building without any days
+pub(crate) fn solve<M: 'static, P: 'static>(
+    model: &Model,
+    params: &Resolved,
+    parse: fn(&str) -> Result<M>,
+    part: impl FnOnce(&M, &P) -> Result<Answer>,
+) -> Result<Answer> {
+    part(downcast(model, parse)?, params.get()?)
+}
+
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::runner;
+
+    #[test]
+    fn test_days_are_in_order() {
+        assert!(YEARS.windows(2).all(|years| years[0].year < years[1].year));
+
+        for year in YEARS {
+            assert!(year.days.iter().all(|d| d.year == year.year));
+            assert!(year.days.windows(2).all(|days| days[0].day < days[1].day));
+        }
+    }
+
+    #[test]
+    fn test_answers() {
+        for year in YEARS {
+            let answers = year.answers().unwrap();
+
+            assert!(answers.keys().all(|day| (1..=25).contains(day)));
+            assert!(answers.values().all(|parts| parts.len() <= 2));
+        }
+    }
+
+    /// Solves every example of the days that are built, the same way the real
+    /// inputs are, only with the parameters from the manifest.
+    #[test]
+    fn test_examples() {
+        for year in YEARS {
+            for (name, example) in year.examples().unwrap() {
+                let day = name
+                    .strip_prefix("day")
+                    .and_then(|name| name.split('_').next())
+                    .and_then(|day| day.parse().ok())
+                    .unwrap_or_else(|| panic!("No day in {name}"));
+                let Ok(day) = get(year.year, day) else {
+                    continue;
+                };
+
+                let path = format!(
+                    "{}/input/{}/{name}.txt",
+                    env!("CARGO_MANIFEST_DIR"),
+                    year.year
+                );
+                let input = std::fs::read_to_string(&path).unwrap();
+                let params = runner::params(day, &example.params).unwrap();
+                let report = runner::solve(day, &input, &params, None).unwrap();
+
+                let stages = [&report.part1, &report.part2];
+                for (stage, expected) in stages.into_iter().zip(&example.answers) {
+                    let answer = stage.answer.as_ref().ok().map(Answer::to_string);
+
+                    assert_eq!(answer.as_ref(), Some(expected), "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_example_manifest() {
+        let examples = crate::y2022::YEAR.examples().unwrap();
+
+        assert_eq!(
+            examples["day15_example"].params,
+            [Override::new("range", "0..=20"), Override::new("row", "10")]
+        );
+        assert_eq!(examples["day11_example"].params, []);
+        assert_eq!(examples["day11_example"].answers, ["10605", "2713310158"]);
+    }
+
+    #[cfg(feature = "y2022-day7")]
+    #[test]
+    fn test_get() {
+        let day = get(2022, 7).unwrap();
+
+        assert_eq!((day.year, day.day), (2022, 7));
+        assert!(get(2022, 26).is_err());
+        assert!(get(1999, 7).is_err());
+    }
+}
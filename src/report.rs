@@ -0,0 +1,476 @@
+use std::{fmt::Write, fs, path::Path, str::FromStr, time::Duration};
+
+use crate::{
+    cancel,
+    prelude::*,
+    registry::{self, Answers},
+    runner::{self, Cost, Report},
+};
+
+/// What the report is written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// How a day went, along with what it was expected to come up with.
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub report: Result<Report>,
+    /// The accepted answers of the parts, as they're displayed.
+    pub expected: Vec<String>,
+}
+
+/// How an answer compares to the accepted one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Verified,
+    Wrong(String),
+    Unverified,
+    TimedOut,
+    Failed(String),
+}
+
+/// A single line of the report's table.
+struct Row<'e> {
+    day: u8,
+    part: &'static str,
+    answer: Option<&'e Answer>,
+    cost: Option<Cost>,
+    status: Option<Status>,
+}
+
+/// Solves every registered day and writes the report to `output`, or to stdout
+/// if there's nowhere to write it.
+pub fn run(format: Format, output: Option<&Path>, timeout: Option<Duration>) -> Result<()> {
+    let entries = solve_all(timeout)?;
+    let report = render(format, &entries);
+
+    match output {
+        Some(path) => fs::write(path, report)
+            .with_context(|| format!("Failed writing the report to {}", path.display())),
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
+
+/// Solves every registered day against its own input, with the puzzle's own
+/// parameters since those are what the accepted answers are for, giving up on
+/// any step that takes longer than `timeout`.
+pub fn solve_all(timeout: Option<Duration>) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+
+    for year in registry::YEARS {
+        let answers: Answers = year.answers()?;
+
+        for day in year.days {
+            entries.push(Entry {
+                year: day.year,
+                day: day.day,
+                report: runner::params(day, &[])
+                    .and_then(|params| runner::solve(day, day.input, &params, timeout)),
+                expected: answers.get(&day.day).cloned().unwrap_or_default(),
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+pub fn render(format: Format, entries: &[Entry]) -> String {
+    match format {
+        Format::Markdown => markdown(entries),
+        Format::Html => html(entries),
+    }
+}
+
+impl Status {
+    pub fn of(answer: &Result<Answer>, expected: Option<&String>) -> Self {
+        match (answer, expected) {
+            (Err(err), _) if cancel::is_cancelled(err) => Status::TimedOut,
+            (Err(err), _) => Status::Failed(format!("{err:#}")),
+            (Ok(answer), Some(expected)) if answer.to_string() == *expected => Status::Verified,
+            (Ok(_), Some(expected)) => Status::Wrong(expected.clone()),
+            (Ok(_), None) => Status::Unverified,
+        }
+    }
+}
+
+impl Entry {
+    fn rows(&self) -> Vec<Row<'_>> {
+        let report = match &self.report {
+            Ok(report) => report,
+            Err(err) => {
+                return vec![Row {
+                    day: self.day,
+                    part: "parse",
+                    answer: None,
+                    cost: None,
+                    status: Some(Status::Failed(format!("{err:#}"))),
+                }]
+            }
+        };
+
+        let parts = [("1", &report.part1), ("2", &report.part2)];
+
+        std::iter::once(Row {
+            day: self.day,
+            part: "parse",
+            answer: None,
+            cost: Some(report.parse),
+            status: None,
+        })
+        .chain(
+            parts
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (part, stage))| Row {
+                    day: self.day,
+                    part,
+                    answer: stage.answer.as_ref().ok(),
+                    cost: Some(stage.cost),
+                    status: Some(Status::of(&stage.answer, self.expected.get(idx))),
+                }),
+        )
+        .collect()
+    }
+
+    /// How both parts went, even if the input couldn't be parsed, which fails
+    /// them both.
+    fn statuses(&self) -> Vec<Status> {
+        match &self.report {
+            Ok(_) => self
+                .rows()
+                .into_iter()
+                .filter_map(|row| row.status)
+                .collect(),
+            Err(err) => vec![Status::Failed(format!("{err:#}")); 2],
+        }
+    }
+
+    /// The image answers, which are too big for the table.
+    fn images(&self) -> Vec<(u8, &Answer)> {
+        let Ok(report) = &self.report else {
+            return vec![];
+        };
+
+        [(1, &report.part1), (2, &report.part2)]
+            .into_iter()
+            .filter_map(|(part, stage)| match &stage.answer {
+                Ok(answer) if answer.is_image() => Some((part, answer)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn summary(entries: &[Entry]) -> String {
+    let statuses = entries.iter().flat_map(Entry::statuses).collect_vec();
+    let verified = statuses.iter().filter(|s| **s == Status::Verified).count();
+
+    format!("{verified} of {} answers verified.", statuses.len())
+}
+
+fn markdown(entries: &[Entry]) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "# Advent of Code\n\n{}", summary(entries)).unwrap();
+
+    for (year, entries) in &entries.iter().group_by(|entry| entry.year) {
+        let entries = entries.collect_vec();
+
+        writeln!(out, "\n## {year}\n").unwrap();
+        writeln!(out, "| Day | Part | Answer | Time | Allocations | Status |").unwrap();
+        writeln!(out, "| --: | :-- | :-- | --: | --: | :-- |").unwrap();
+
+        for row in entries.iter().flat_map(|entry| entry.rows()) {
+            let answer = match row.answer {
+                Some(answer) if answer.is_image() => "see below".to_string(),
+                Some(answer) => code(&answer.to_string()),
+                None => String::new(),
+            };
+            let status = match &row.status {
+                None => String::new(),
+                Some(Status::Verified) => "verified".to_string(),
+                Some(Status::Wrong(expected)) if expected.contains('\n') => "**wrong**".to_string(),
+                Some(Status::Wrong(expected)) => format!("**wrong**, expected {}", code(expected)),
+                Some(Status::Unverified) => "unverified".to_string(),
+                Some(Status::TimedOut) => "**TIMEOUT**".to_string(),
+                Some(Status::Failed(err)) => format!("**failed**: {err}"),
+            };
+
+            writeln!(
+                out,
+                "| {} | {} | {} | {} | {} | {} |",
+                row.day,
+                row.part,
+                escape_cell(&answer),
+                time(row.cost),
+                allocations(row.cost),
+                escape_cell(&status)
+            )
+            .unwrap();
+        }
+
+        for entry in entries {
+            for (part, image) in entry.images() {
+                writeln!(out, "\n### Day {} part {part}\n", entry.day).unwrap();
+                writeln!(out, "```\n{image}\n```").unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+fn html(entries: &[Entry]) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ padding: 0.2em 0.8em; text-align: left; }}
+td.number {{ text-align: right; }}
+tr.verified td.status {{ color: green; }}
+tr.wrong td.status, tr.failed td.status {{ color: red; font-weight: bold; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code</h1>
+<p>{}</p>"#,
+        summary(entries)
+    )
+    .unwrap();
+
+    for (year, entries) in &entries.iter().group_by(|entry| entry.year) {
+        let entries = entries.collect_vec();
+
+        writeln!(out, "<h2>{year}</h2>\n<table>").unwrap();
+        writeln!(out, "<tr><th>Day</th><th>Part</th><th>Answer</th><th>Time</th><th>Allocations</th><th>Status</th></tr>").unwrap();
+
+        for row in entries.iter().flat_map(|entry| entry.rows()) {
+            let answer = match row.answer {
+                Some(answer) if answer.is_image() => "see below".to_string(),
+                Some(answer) => format!("<code>{}</code>", escape_html(&answer.to_string())),
+                None => String::new(),
+            };
+            let (class, status) = match &row.status {
+                None => ("", String::new()),
+                Some(Status::Verified) => ("verified", "verified".to_string()),
+                Some(Status::Wrong(expected)) if expected.contains('\n') => {
+                    ("wrong", "wrong".to_string())
+                }
+                Some(Status::Wrong(expected)) => (
+                    "wrong",
+                    format!("wrong, expected <code>{}</code>", escape_html(expected)),
+                ),
+                Some(Status::Unverified) => ("unverified", "unverified".to_string()),
+                Some(Status::TimedOut) => ("failed", "TIMEOUT".to_string()),
+                Some(Status::Failed(err)) => ("failed", format!("failed: {}", escape_html(err))),
+            };
+
+            writeln!(
+                out,
+                r#"<tr class="{class}"><td class="number">{}</td><td>{}</td><td>{answer}</td><td class="number">{}</td><td class="number">{}</td><td class="status">{status}</td></tr>"#,
+                row.day,
+                row.part,
+                time(row.cost),
+                allocations(row.cost),
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "</table>").unwrap();
+
+        for entry in entries {
+            for (part, image) in entry.images() {
+                writeln!(out, "<h3>Day {} part {part}</h3>", entry.day).unwrap();
+                writeln!(out, "<pre>{}</pre>", escape_html(&image.to_string())).unwrap();
+            }
+        }
+    }
+
+    writeln!(out, "</body>\n</html>").unwrap();
+
+    out
+}
+
+fn time(cost: Option<Cost>) -> String {
+    cost.map_or_else(String::new, |cost| format!("{:?}", cost.elapsed))
+}
+
+fn allocations(cost: Option<Cost>) -> String {
+    match cost {
+        Some(Cost {
+            allocs: Some(allocs),
+            ..
+        }) => allocs.allocations.to_string(),
+        Some(_) => "-".to_string(),
+        None => String::new(),
+    }
+}
+
+/// Wraps text in a Markdown code span, fenced with more backticks than it has in
+/// a row, and padded when it starts or ends with one so they're kept.
+fn code(s: &str) -> String {
+    let longest = s
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest + 1);
+    let pad = if s.starts_with('`') || s.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+
+    format!("{fence}{pad}{s}{pad}{fence}")
+}
+
+/// Keeps a table cell on a single line, without ending it early.
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl Format {
+    /// Works out the format from a file's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" | "htm" => Ok(Format::Html),
+            _ => bail!("Unknown report format {s}, expected markdown or html"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{alloc::AllocStats, runner::Stage, snapshot::assert_snapshot};
+    use test_case::test_case;
+
+    fn cost(micros: u64, allocations: Option<usize>) -> Cost {
+        Cost {
+            elapsed: Duration::from_micros(micros),
+            allocs: allocations.map(|allocations| AllocStats {
+                allocations,
+                bytes: allocations * 64,
+                peak: 64,
+            }),
+        }
+    }
+
+    fn stage(answer: Result<Answer>, micros: u64) -> Stage {
+        Stage {
+            answer,
+            cost: cost(micros, Some(3)),
+        }
+    }
+
+    fn entries() -> Vec<Entry> {
+        vec![
+            Entry {
+                year: 2022,
+                day: 5,
+                report: Ok(Report {
+                    year: 2022,
+                    day: 5,
+                    parse: cost(120, Some(40)),
+                    part1: stage(Ok("CMZ".into()), 15),
+                    part2: stage(Ok("M`Z|C".into()), 16),
+                }),
+                expected: vec!["CMZ".to_string(), "MCD".to_string()],
+            },
+            Entry {
+                year: 2022,
+                day: 10,
+                report: Ok(Report {
+                    year: 2022,
+                    day: 10,
+                    parse: cost(80, None),
+                    part1: stage(Err(anyhow!("Cycle | overflow")), 3),
+                    part2: stage(Ok(Answer::Image("#..#\n#<>#".to_string())), 100),
+                }),
+                expected: vec!["13140".to_string()],
+            },
+            Entry {
+                year: 2022,
+                day: 16,
+                report: Err(anyhow!("Failed parsing 2022 day 16")),
+                expected: vec![],
+            },
+        ]
+    }
+
+    #[test_case(Ok(Answer::Integer(24)), Some("24"), Status::Verified ; "verified")]
+    #[test_case(Ok(Answer::Integer(24)), Some("93"), Status::Wrong("93".to_string()) ; "wrong")]
+    #[test_case(Ok(Answer::Integer(24)), None, Status::Unverified ; "unverified")]
+    #[test_case(Err(anyhow!("No crane")), Some("CMZ"), Status::Failed("No crane".to_string()) ; "failed")]
+    #[test_case(Err(cancel::Cancelled.into()), Some("1707"), Status::TimedOut ; "timed out")]
+    fn test_status(answer: Result<Answer>, expected: Option<&str>, status: Status) {
+        assert_eq!(
+            Status::of(&answer, expected.map(str::to_string).as_ref()),
+            status
+        );
+    }
+
+    #[test_case("report.md", Some(Format::Markdown))]
+    #[test_case("out/report.html", Some(Format::Html))]
+    #[test_case("report.txt", None)]
+    #[test_case("report", None)]
+    fn test_format_from_path(path: &str, expected: Option<Format>) {
+        assert_eq!(Format::from_path(Path::new(path)), expected);
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(&entries()), "1 of 6 answers verified.");
+    }
+
+    #[test_case("CMZ", "`CMZ`" ; "plain")]
+    #[test_case("M`Z", "``M`Z``" ; "backtick")]
+    #[test_case("``A`", "``` ``A` ```" ; "backticks at the ends")]
+    fn test_code(s: &str, expected: &str) {
+        assert_eq!(code(s), expected);
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_snapshot("report/markdown", &render(Format::Markdown, &entries()));
+    }
+
+    #[test]
+    fn test_html() {
+        assert_snapshot("report/html", &render(Format::Html, &entries()));
+    }
+}
@@ -0,0 +1,235 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde_json::json;
+
+use crate::{
+    alloc::{self, AllocStats},
+    cache::Cache,
+    cancel::{self, CancellationToken},
+    params::{Override, Resolved},
+    prelude::*,
+    progress,
+    registry::Day,
+};
+
+/// How a single day went: what parsing cost, and what each part came up with.
+pub struct Report {
+    pub year: u16,
+    pub day: u8,
+    pub parse: Cost,
+    pub part1: Stage,
+    pub part2: Stage,
+}
+
+pub struct Stage {
+    pub answer: Result<Answer>,
+    pub cost: Cost,
+}
+
+/// What running a step took. Allocations are only counted when the binary is
+/// built with the `alloc-stats` feature.
+#[derive(Debug, Clone, Copy)]
+pub struct Cost {
+    pub elapsed: Duration,
+    pub allocs: Option<AllocStats>,
+}
+
+/// Solves the day against its own puzzle input and prints the report, unless
+/// its answers are already cached. Fresh answers are cached for next time.
+pub fn run(
+    day: &Day,
+    overrides: &[Override],
+    cache: &mut Cache,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let params = params(day, overrides)?;
+
+    if let Some([part1, part2]) = cache.get(day, &params) {
+        println!("{day} (cached)");
+        println!("part 1{}", Cached(part1));
+        println!("part 2{}", Cached(part2));
+
+        return Ok(());
+    }
+
+    let report = solve(day, day.input, &params, timeout)?;
+
+    println!("{report}");
+
+    cache.insert(day, &params, &report);
+    cache.save()
+}
+
+/// Solves the day against some other input, such as an example, printing the
+/// report as text or as JSON. Other inputs are never cached.
+pub fn run_on(
+    day: &Day,
+    input: &str,
+    overrides: &[Override],
+    json: bool,
+    timeout: Option<Duration>,
+) -> Result<()> {
+    let report = solve(day, input, &params(day, overrides)?, timeout)?;
+
+    if json {
+        println!("{}", report.to_json());
+    } else {
+        println!("{report}");
+    }
+
+    Ok(())
+}
+
+/// Parses the input once, then solves both parts against the parsed model,
+/// measuring each step. Only a failure to parse is an error, a failing part is
+/// kept in its [`Stage`] so the other part still gets a go.
+///
+/// Each step is cancelled once it's taken longer than `timeout`, which shows
+/// as `TIMEOUT` in its place, and shows its progress while it's running if it
+/// reports any.
+pub fn solve(
+    day: &Day,
+    input: &str,
+    params: &Resolved,
+    timeout: Option<Duration>,
+) -> Result<Report> {
+    let (model, parse) = task(&format!("{day} parse"), timeout, || (day.parse)(input));
+    let model = model.with_context(|| format!("Failed parsing {day}"))?;
+
+    let part1 = Stage::from(task(&format!("{day} part 1"), timeout, || {
+        (day.part1)(&model, params)
+    }));
+    let part2 = Stage::from(task(&format!("{day} part 2"), timeout, || {
+        (day.part2)(&model, params)
+    }));
+
+    Ok(Report {
+        year: day.year,
+        day: day.day,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+/// The day's parameters, the puzzle's own unless overridden.
+pub fn params(day: &Day, overrides: &[Override]) -> Result<Resolved> {
+    (day.params)(overrides).with_context(|| format!("Invalid parameters for {day}"))
+}
+
+/// Runs and measures a single step, with a token that's cancelled once it's
+/// taken longer than `timeout`, which only stops the step early if it checks
+/// the token. Its progress is watched from outside of the
+/// measurement, so drawing it doesn't count towards the step's cost.
+fn task<T>(
+    label: &str,
+    timeout: Option<Duration>,
+    f: impl FnOnce() -> Result<T>,
+) -> (Result<T>, Cost) {
+    let token = timeout.map_or_else(CancellationToken::new, CancellationToken::with_timeout);
+
+    progress::watch(label, || measure(|| token.scope(f)))
+}
+
+impl Report {
+    /// The answers of both parts, or why they failed, as they're displayed.
+    pub fn to_json(&self) -> serde_json::Value {
+        let stage = |stage: &Stage| match &stage.answer {
+            Ok(answer) => json!({ "answer": answer.to_string() }),
+            Err(err) => json!({ "error": format!("{err:#}") }),
+        };
+
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part1": stage(&self.part1),
+            "part2": stage(&self.part2),
+        })
+    }
+}
+
+impl From<(Result<Answer>, Cost)> for Stage {
+    fn from((answer, cost): (Result<Answer>, Cost)) -> Self {
+        Self { answer, cost }
+    }
+}
+
+/// Runs `f`, returning its result along with the time it took and, if they're
+/// being counted, its allocations.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Cost) {
+    if cfg!(feature = "alloc-stats") {
+        let ((res, elapsed), allocs) = alloc::measure(|| time(f));
+
+        (
+            res,
+            Cost {
+                elapsed,
+                allocs: Some(allocs),
+            },
+        )
+    } else {
+        let (res, elapsed) = time(f);
+
+        (
+            res,
+            Cost {
+                elapsed,
+                allocs: None,
+            },
+        )
+    }
+}
+
+/// Runs `f`, returning its result and the wall-clock time it took.
+pub fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let res = f();
+
+    (res, start.elapsed())
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} day {}", self.year, self.day)?;
+        writeln!(f, "parse  [{}]", self.parse)?;
+        writeln!(f, "part 1 {}", self.part1)?;
+        write!(f, "part 2 {}", self.part2)
+    }
+}
+
+impl Display for Stage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.answer {
+            Ok(answer) if answer.is_image() => write!(f, "[{}]:\n{answer}", self.cost),
+            Ok(answer) => write!(f, "[{}]: {answer}", self.cost),
+            Err(err) if cancel::is_cancelled(err) => write!(f, "[{}]: TIMEOUT", self.cost),
+            Err(err) => write!(f, "[{}]: failed: {err:#}", self.cost),
+        }
+    }
+}
+
+struct Cached<'a>(&'a Answer);
+
+impl Display for Cached<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            answer if answer.is_image() => write!(f, " [cached]:\n{answer}"),
+            answer => write!(f, " [cached]: {answer}"),
+        }
+    }
+}
+
+impl Display for Cost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.elapsed)?;
+
+        if let Some(allocs) = &self.allocs {
+            write!(f, ", {allocs}")?;
+        }
+
+        Ok(())
+    }
+}
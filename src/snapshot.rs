@@ -0,0 +1,90 @@
+use std::{env, fs, path::PathBuf};
+
+use itertools::{EitherOrBoth, Itertools};
+
+/// Set to anything but `0` to write the rendered output over the stored
+/// snapshots instead of comparing against them.
+const UPDATE: &str = "UPDATE_SNAPSHOTS";
+
+/// Compares rendered output against the snapshot stored at
+/// `snapshots/{name}.snap`, failing with a line diff if they differ.
+///
+/// Trailing newlines are ignored on both sides, so snapshots can be opened and
+/// saved in any editor.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = path(name);
+    let actual = actual.trim_end_matches('\n');
+
+    if updating() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, format!("{actual}\n")).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "No snapshot at {}, run with {UPDATE}=1 to create it",
+            path.display()
+        )
+    });
+    let expected = expected.trim_end_matches('\n');
+
+    if expected != actual {
+        panic!(
+            "Snapshot {name} does not match, run with {UPDATE}=1 to update it\n{}",
+            diff(expected, actual)
+        );
+    }
+}
+
+fn path(name: &str) -> PathBuf {
+    [
+        env!("CARGO_MANIFEST_DIR"),
+        "snapshots",
+        &format!("{name}.snap"),
+    ]
+    .iter()
+    .collect()
+}
+
+fn updating() -> bool {
+    env::var(UPDATE).is_ok_and(|v| v != "0")
+}
+
+/// Lists both sides line by line, marking what only the snapshot has with `-`
+/// and what only the output has with `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    expected
+        .lines()
+        .zip_longest(actual.lines())
+        .flat_map(|lines| match lines {
+            EitherOrBoth::Both(e, a) if e == a => vec![format!("  {e}")],
+            EitherOrBoth::Both(e, a) => vec![format!("- {e}"), format!("+ {a}")],
+            EitherOrBoth::Left(e) => vec![format!("- {e}")],
+            EitherOrBoth::Right(a) => vec![format!("+ {a}")],
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("#.\n.#", "#.\n.#", "  #.\n  .#" ; "same")]
+    #[test_case("#.\n.#", "#.\n##", "  #.\n- .#\n+ ##" ; "changed")]
+    #[test_case("#.", "#.\n.#", "  #.\n+ .#" ; "added")]
+    #[test_case("#.\n.#", "#.", "  #.\n- .#" ; "removed")]
+    fn test_diff(expected: &str, actual: &str, diffed: &str) {
+        assert_eq!(diff(expected, actual), diffed);
+    }
+
+    #[test]
+    fn test_path() {
+        let path = path("2022/day10_example_part2");
+
+        assert!(path.ends_with("snapshots/2022/day10_example_part2.snap"));
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute, queue,
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    params::{Override, Resolved},
+    prelude::*,
+    registry::{Day, Model},
+    runner,
+};
+
+/// Builds the simulation of a part out of a day's parsed input and parameters.
+pub type Visualise = fn(&Model, &Resolved, u8) -> Result<Box<dyn Simulation>>;
+
+/// A puzzle that can be watched while it's being solved, one step at a time.
+pub trait Simulation {
+    /// Advances by a single step. Returns `false` once there's nothing left to
+    /// do, without changing anything.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state.
+    fn frame(&self) -> Frame;
+}
+
+/// A single drawing of a simulation, as plain text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    pub grid: Vec<String>,
+    /// The `(row, column)` of the grid to keep in view when it doesn't fit in
+    /// the terminal, such as the head of the rope.
+    pub focus: Option<(usize, usize)>,
+}
+
+/// How often to dump frames when running without a terminal.
+#[derive(Debug, Clone, Copy)]
+pub struct Headless {
+    /// Dump every n-th step.
+    pub every: usize,
+    /// Stop after this many frames.
+    pub frames: Option<usize>,
+}
+
+impl Default for Headless {
+    fn default() -> Self {
+        Self {
+            every: 1,
+            frames: None,
+        }
+    }
+}
+
+/// Parses the day's own input and visualises the given part, with the puzzle's
+/// parameters unless overridden, either in the terminal or by dumping the
+/// frames to stdout.
+pub fn run(day: &Day, part: u8, overrides: &[Override], headless: Option<Headless>) -> Result<()> {
+    let visualise = day
+        .visualise
+        .ok_or_else(|| anyhow!("{day} has nothing to visualise"))?;
+
+    let params = runner::params(day, overrides)?;
+    let model = (day.parse)(day.input).with_context(|| format!("Failed parsing {day}"))?;
+    let mut simulation = visualise(&model, &params, part)?;
+
+    match headless {
+        Some(headless) => {
+            dump(simulation.as_mut(), headless, &mut io::stdout().lock())?;
+            Ok(())
+        }
+        None => interactive(simulation.as_mut()),
+    }
+}
+
+/// Writes the starting frame, then a frame after every `headless.every` steps,
+/// and the final frame, separated by blank lines. Returns how many frames were
+/// written.
+pub fn dump(
+    simulation: &mut dyn Simulation,
+    headless: Headless,
+    out: &mut impl Write,
+) -> Result<usize> {
+    let every = headless.every.max(1);
+    let limit = headless.frames.unwrap_or(usize::MAX);
+
+    writeln!(out, "{}", simulation.frame())?;
+    let mut frames = 1;
+
+    while frames < limit {
+        let mut steps = 0;
+        while steps < every && simulation.step() {
+            steps += 1;
+        }
+
+        if steps == 0 {
+            break;
+        }
+
+        writeln!(out, "\n{}", simulation.frame())?;
+        frames += 1;
+
+        if steps < every {
+            break;
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Plays the simulation in the terminal until it's quit.
+///
+/// Space plays and pauses, the right arrow or `n` steps once, `+` and `-`
+/// change the speed, and `q` or escape quits.
+pub fn interactive(simulation: &mut dyn Simulation) -> Result<()> {
+    let _terminal = RawTerminal::enter()?;
+    let mut out = io::stdout().lock();
+
+    let mut player = Player::default();
+
+    loop {
+        draw(&mut out, &simulation.frame(), &player)?;
+
+        let timeout = player.playing.then_some(player.speed.delay);
+        let event = match timeout {
+            Some(timeout) if !event::poll(timeout)? => None,
+            _ => Some(event::read()?),
+        };
+
+        let Some(event) = event else {
+            player.advance(simulation, player.speed.steps);
+            continue;
+        };
+
+        if let Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        {
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => player.playing = !player.playing && !player.done,
+                KeyCode::Right | KeyCode::Char('n') => {
+                    player.playing = false;
+                    player.advance(simulation, 1);
+                }
+                KeyCode::Char('+') | KeyCode::Up => player.speed.faster(),
+                KeyCode::Char('-') | KeyCode::Down => player.speed.slower(),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct Player {
+    playing: bool,
+    done: bool,
+    steps: usize,
+    speed: Speed,
+}
+
+impl Player {
+    fn advance(&mut self, simulation: &mut dyn Simulation, steps: usize) {
+        for _ in 0..steps {
+            if !simulation.step() {
+                self.done = true;
+                self.playing = false;
+                return;
+            }
+
+            self.steps += 1;
+        }
+    }
+}
+
+/// How long to wait between frames, and how many steps to take per frame once
+/// waiting any less isn't possible.
+struct Speed {
+    delay: Duration,
+    steps: usize,
+}
+
+impl Speed {
+    const MIN_DELAY: Duration = Duration::from_millis(1);
+    const MAX_DELAY: Duration = Duration::from_secs(2);
+    /// A power of two, so slowing down again halves it back to a single step.
+    const MAX_STEPS: usize = 1 << 16;
+
+    fn faster(&mut self) {
+        if self.delay > Self::MIN_DELAY {
+            self.delay = (self.delay / 2).max(Self::MIN_DELAY);
+        } else {
+            self.steps = (self.steps * 2).min(Self::MAX_STEPS);
+        }
+    }
+
+    fn slower(&mut self) {
+        if self.steps > 1 {
+            self.steps /= 2;
+        } else {
+            self.delay = (self.delay * 2).min(Self::MAX_DELAY);
+        }
+    }
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(128),
+            steps: 1,
+        }
+    }
+}
+
+/// Puts the terminal in raw mode on the alternate screen, and restores it when
+/// dropped, even if the simulation bails.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn draw(out: &mut impl Write, frame: &Frame, player: &Player) -> Result<()> {
+    let (columns, rows) = terminal::size()?;
+    let (columns, rows) = (columns as usize, rows as usize);
+
+    // the title and the status line take a row each
+    let height = rows.saturating_sub(2);
+    let width = frame.grid.iter().map(|line| line.len()).max().unwrap_or(0);
+    let (top, left) = frame.focus.map_or((0, 0), |(row, column)| {
+        (
+            viewport_start(row, height, frame.grid.len()),
+            viewport_start(column, columns, width),
+        )
+    });
+
+    queue!(out, cursor::MoveTo(0, 0), terminal::Clear(ClearType::All))?;
+    write!(out, "\x1b[1m{}\x1b[0m\r\n", frame.title)?;
+
+    for line in frame.grid.iter().skip(top).take(height) {
+        for c in line.chars().skip(left).take(columns) {
+            match colour(c) {
+                Some(colour) => write!(out, "{colour}{c}\x1b[0m")?,
+                None => write!(out, "{c}")?,
+            }
+        }
+        write!(out, "\r\n")?;
+    }
+
+    queue!(out, cursor::MoveTo(0, rows.saturating_sub(1) as u16))?;
+    write!(out, "\x1b[7m{}\x1b[0m", player.status())?;
+
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Where the visible part of a line of `len` cells starts, so that `focus`
+/// sits in the middle of `size` cells, without scrolling past either end.
+fn viewport_start(focus: usize, size: usize, len: usize) -> usize {
+    focus.saturating_sub(size / 2).min(len.saturating_sub(size))
+}
+
+fn colour(c: char) -> Option<&'static str> {
+    match c {
+        'H' | '+' | 's' => Some("\x1b[1;31m"),
+        'T' | '1'..='9' => Some("\x1b[36m"),
+        'o' | '~' => Some("\x1b[33m"),
+        '#' => Some("\x1b[90m"),
+        'A'..='Z' => Some("\x1b[32m"),
+        _ => None,
+    }
+}
+
+impl Player {
+    fn status(&self) -> String {
+        let state = match (self.done, self.playing) {
+            (true, _) => "done",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+
+        format!(
+            " {state} | step {} | {:?} x{} | space: play/pause, n: step, +/-: speed, q: quit ",
+            self.steps, self.speed.delay, self.speed.steps
+        )
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title)?;
+
+        for line in &self.grid {
+            write!(f, "\n{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    /// Counts up to a limit, drawing the current count.
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+
+            self.count += 1;
+            true
+        }
+
+        fn frame(&self) -> Frame {
+            Frame {
+                title: format!("count {}", self.count),
+                grid: vec!["#".repeat(self.count)],
+                focus: None,
+            }
+        }
+    }
+
+    #[test_case(1, None, &[0, 1, 2, 3] ; "every step")]
+    #[test_case(2, None, &[0, 2, 3] ; "every other step")]
+    #[test_case(3, None, &[0, 3] ; "exactly to the end")]
+    #[test_case(1, Some(2), &[0, 1] ; "limited")]
+    fn test_dump(every: usize, frames: Option<usize>, expected: &[usize]) {
+        let mut counter = Counter { count: 0, limit: 3 };
+        let mut out = Vec::new();
+
+        let written = dump(&mut counter, Headless { every, frames }, &mut out).unwrap();
+
+        let expected = expected
+            .iter()
+            .map(|count| format!("count {count}\n{}\n", "#".repeat(*count)))
+            .join("\n");
+
+        assert_eq!(written, expected.matches("count").count());
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test_case(0, 10, 100, 0 ; "start")]
+    #[test_case(50, 10, 100, 45 ; "middle")]
+    #[test_case(99, 10, 100, 90 ; "end")]
+    #[test_case(3, 10, 5, 0 ; "fits")]
+    fn test_viewport_start(focus: usize, size: usize, len: usize, expected: usize) {
+        assert_eq!(viewport_start(focus, size, len), expected);
+    }
+
+    #[test]
+    fn test_speed() {
+        let mut speed = Speed::default();
+
+        (0..10).for_each(|_| speed.faster());
+        assert_eq!((speed.delay, speed.steps), (Speed::MIN_DELAY, 8));
+
+        (0..20).for_each(|_| speed.slower());
+        assert_eq!((speed.delay, speed.steps), (Speed::MAX_DELAY, 1));
+
+        (0..100).for_each(|_| speed.faster());
+        assert_eq!(
+            (speed.delay, speed.steps),
+            (Speed::MIN_DELAY, Speed::MAX_STEPS)
+        );
+
+        (0..30).for_each(|_| speed.slower());
+        assert_eq!((speed.delay, speed.steps), (Speed::MAX_DELAY, 1));
+    }
+}
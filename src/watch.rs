@@ -0,0 +1,352 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crossterm::{cursor, execute, terminal};
+use serde_json::Value;
+
+use crate::{cache, params::Override, prelude::*, registry};
+
+/// How often the watched files are checked for changes.
+const POLL: Duration = Duration::from_millis(500);
+
+/// How a part went against one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Answer(String),
+    Failed(String),
+}
+
+/// Both parts' outcomes against a single input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub name: String,
+    pub parts: [Outcome; 2],
+}
+
+/// Reruns a day whenever its source, any of its inputs or the code it shares
+/// with every other day change, showing the answers for the examples and the real input side by side, along with what
+/// changed since the last run.
+///
+/// The day is rebuilt and run through cargo, with only its own feature
+/// enabled and a target directory of its own, so rebuilding stays quick and
+/// doesn't throw away the regular build. Examples are run with the parameters
+/// the manifest gives them.
+pub fn run(year: u16, day: u8) -> Result<()> {
+    let source = root().join(format!("src/y{year}/day{day}.rs"));
+    let inputs = inputs(&root().join(format!("input/{year}")), day)?;
+    let examples = registry::year(year)?.examples()?;
+
+    let shared = cache::SHARED
+        .iter()
+        .map(|(path, _)| root().join(path))
+        .collect_vec();
+
+    let watched = [source.clone()]
+        .into_iter()
+        .chain(inputs.iter().cloned())
+        .chain(shared.iter().cloned())
+        .collect_vec();
+
+    let mut stamps = None;
+    let mut previous: Option<Vec<Column>> = None;
+
+    loop {
+        let current = watched.iter().map(|path| modified(path)).collect_vec();
+        if stamps.as_ref() == Some(&current) {
+            thread::sleep(POLL);
+            continue;
+        }
+        stamps = Some(current);
+
+        execute!(
+            io::stdout(),
+            terminal::Clear(terminal::ClearType::All),
+            cursor::MoveTo(0, 0)
+        )?;
+        println!(
+            "watching {}, {} input(s) and {} shared file(s)\n",
+            source.display(),
+            inputs.len(),
+            shared.len()
+        );
+
+        let columns = inputs
+            .iter()
+            .map(|input| {
+                let params = input
+                    .file_stem()
+                    .and_then(|name| examples.get(name.to_str()?))
+                    .map_or(&[][..], |example| &example.params);
+
+                solve(year, day, input, params)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        println!(
+            "{}",
+            render(&format!("{year} day {day}"), &columns, previous.as_deref())
+        );
+
+        previous = Some(columns);
+    }
+}
+
+/// The project the binary was built from, which is where the sources and
+/// inputs being watched live.
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// The day's examples, in the order of their names, followed by its input.
+pub fn inputs(dir: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let prefix = format!("day{day}_");
+
+    let mut examples = fs::read_dir(dir)
+        .with_context(|| format!("Failed reading {}", dir.display()))?
+        .map(|entry| Ok(entry?.path()))
+        .filter_ok(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.contains("example"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    examples.sort();
+
+    let input = dir.join(format!("day{day}.txt"));
+    if !input.exists() {
+        bail!("No input for day {day} at {}", input.display());
+    }
+
+    examples.push(input);
+
+    Ok(examples)
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Builds and runs the day against `input`. A build or parse failure fails
+/// both parts, with the error output as the reason.
+fn solve(year: u16, day: u8, input: &Path, params: &[Override]) -> Result<Column> {
+    let name = input.file_stem().map_or_else(
+        || input.display().to_string(),
+        |s| s.to_string_lossy().to_string(),
+    );
+
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    let output = Command::new(cargo)
+        .current_dir(root())
+        .args(["run", "--quiet", "--release", "--no-default-features"])
+        .args(["--features", &format!("y{year}-day{day}")])
+        .args(["--target-dir", "target/watch", "--"])
+        .args([
+            "run",
+            &year.to_string(),
+            &day.to_string(),
+            "--json",
+            "--input",
+        ])
+        .arg(input)
+        .args(
+            params
+                .iter()
+                .flat_map(|o| ["--param".to_string(), o.to_string()]),
+        )
+        .output()
+        .context("Failed running cargo")?;
+
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let failed = Outcome::Failed(err.lines().last().unwrap_or("failed").to_string());
+
+        // compiler errors go to the terminal as they are, they don't fit a table
+        eprintln!("{err}\n");
+
+        return Ok(Column {
+            name,
+            parts: [failed.clone(), failed],
+        });
+    }
+
+    let report: Value = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Failed reading the answers for {}", input.display()))?;
+
+    Ok(Column {
+        name,
+        parts: [outcome(&report["part1"]), outcome(&report["part2"])],
+    })
+}
+
+fn outcome(part: &Value) -> Outcome {
+    match (part["answer"].as_str(), part["error"].as_str()) {
+        (Some(answer), _) => Outcome::Answer(answer.to_string()),
+        (None, Some(err)) => Outcome::Failed(err.to_string()),
+        (None, None) => Outcome::Failed(format!("unexpected output: {part}")),
+    }
+}
+
+/// Lays the inputs out as columns and the parts as rows. An outcome that's
+/// different from the previous run is marked with `*` and followed by what it
+/// was before.
+pub fn render(title: &str, columns: &[Column], previous: Option<&[Column]>) -> String {
+    let header = std::iter::once(title.to_string())
+        .chain(columns.iter().map(|column| column.name.clone()))
+        .collect_vec();
+
+    let rows = (0..2)
+        .map(|part| {
+            std::iter::once(format!("part {}", part + 1))
+                .chain(columns.iter().map(|column| {
+                    let before = previous
+                        .and_then(|previous| previous.iter().find(|c| c.name == column.name))
+                        .map(|c| &c.parts[part]);
+
+                    cell(&column.parts[part], before)
+                }))
+                .collect_vec()
+        })
+        .collect_vec();
+
+    table(&[header].into_iter().chain(rows).collect_vec())
+}
+
+fn cell(outcome: &Outcome, before: Option<&Outcome>) -> String {
+    let text = |outcome: &Outcome| match outcome {
+        Outcome::Answer(answer) => answer.clone(),
+        Outcome::Failed(err) => format!("failed: {err}"),
+    };
+
+    match before {
+        Some(before) if before != outcome => {
+            format!("* {}\n  (was {})", text(outcome), text(before))
+        }
+        _ => text(outcome),
+    }
+}
+
+/// Aligns the cells into columns. Cells can span several lines, such as the
+/// CRT image of day 10, which makes their whole row that much taller.
+fn table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .flat_map(|cell| cell.lines())
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect_vec();
+    let widths = &widths;
+
+    rows.iter()
+        .flat_map(|row| {
+            let height = row
+                .iter()
+                .map(|cell| cell.lines().count())
+                .max()
+                .unwrap_or(1);
+
+            (0..height.max(1)).map(move |line| {
+                row.iter()
+                    .zip(widths)
+                    .map(|(cell, width)| format!("{:width$}", cell.lines().nth(line).unwrap_or("")))
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            })
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn column(name: &str, part1: &str, part2: Outcome) -> Column {
+        Column {
+            name: name.to_string(),
+            parts: [Outcome::Answer(part1.to_string()), part2],
+        }
+    }
+
+    #[test]
+    fn test_inputs() {
+        let inputs = inputs(&root().join("input/2022"), 9).unwrap();
+
+        let names = inputs
+            .iter()
+            .map(|path| path.file_name().unwrap().to_str().unwrap())
+            .collect_vec();
+
+        assert_eq!(
+            names,
+            ["day9_example.txt", "day9_example_large.txt", "day9.txt"]
+        );
+    }
+
+    #[test]
+    fn test_inputs_missing() {
+        assert!(inputs(&root().join("input/2022"), 25).is_err());
+    }
+
+    #[test]
+    fn test_shared_files_exist() {
+        for (path, _) in cache::SHARED {
+            assert!(root().join(path).is_file(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let columns = [
+            column("day16_example", "1651", Outcome::Answer("1707".to_string())),
+            column("day16", "1871", Outcome::Failed("No valves".to_string())),
+        ];
+
+        let expected = "\
+2022 day 16  day16_example  day16
+part 1       1651           1871
+part 2       1707           failed: No valves";
+
+        assert_eq!(render("2022 day 16", &columns, None), expected);
+    }
+
+    #[test]
+    fn test_render_changes() {
+        let previous = [
+            column(
+                "day10_example",
+                "13140",
+                Outcome::Answer("#.\n.#".to_string()),
+            ),
+            column("day10", "15880", Outcome::Answer("..\n##".to_string())),
+        ];
+        let columns = [
+            column(
+                "day10_example",
+                "13140",
+                Outcome::Answer("#.\n.#".to_string()),
+            ),
+            column("day10", "15881", Outcome::Answer("..\n##".to_string())),
+        ];
+
+        let expected = "\
+2022 day 10  day10_example  day10
+part 1       13140          * 15881
+                              (was 15880)
+part 2       #.             ..
+             .#             ##";
+
+        assert_eq!(render("2022 day 10", &columns, Some(&previous)), expected);
+    }
+}
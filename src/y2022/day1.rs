@@ -0,0 +1,366 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt::Display,
+    io::BufRead,
+};
+
+use crate::{params, prelude::*};
+
+pub const INPUT: &str = include_str!("../../input/2022/day1.txt");
+
+/// How many of the elves carrying the most calories part 2 adds up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub top: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { top: 3 }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "top" => self.top = value.parse()?,
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Elf>> {
+    elves(input.as_bytes()).collect()
+}
+
+pub fn part1(elves: &[Elf], _params: &Params) -> Result<Answer> {
+    top(elves.iter().copied(), 1)
+        .first()
+        .map(|elf| Answer::from(elf.total))
+        .ok_or_else(|| anyhow!("No elves"))
+}
+
+pub fn part2(elves: &[Elf], params: &Params) -> Result<Answer> {
+    let top = top(elves.iter().copied(), params.top);
+
+    top.iter()
+        .try_fold(0_usize, |sum, elf| sum.checked_add(elf.total))
+        .map(Answer::from)
+        .ok_or_else(|| {
+            anyhow!(
+                "The top {} elves carry too many calories to add up",
+                params.top
+            )
+        })
+}
+
+/// What a single elf is carrying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    /// Where the elf is in the input, counting from 0.
+    pub index: usize,
+    /// The calories of all of its items.
+    pub total: usize,
+    pub items: usize,
+}
+
+/// Elves carrying more come first, and of those carrying the same the one
+/// that came first in the input does.
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total
+            .cmp(&other.total)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Elf {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "elf {}: {} calories in {} items",
+            self.index, self.total, self.items
+        )
+    }
+}
+
+/// Reads the elves one at a time, adding up each block of lines as it goes, so
+/// an input of any size is read in constant memory. Blank lines separate the
+/// elves, and any other line that isn't a number is an error.
+pub fn elves<R: BufRead>(reader: R) -> Elves<R> {
+    Elves {
+        reader,
+        line: String::new(),
+        line_no: 0,
+        index: 0,
+        done: false,
+    }
+}
+
+pub struct Elves<R> {
+    reader: R,
+    /// Reused for every line read.
+    line: String,
+    line_no: usize,
+    index: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Iterator for Elves<R> {
+    type Item = Result<Elf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut elf = Elf {
+            index: self.index,
+            total: 0,
+            items: 0,
+        };
+
+        while !self.done {
+            self.line.clear();
+            let read = match self.reader.read_line(&mut self.line) {
+                Ok(read) => read,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                }
+            };
+            self.line_no += 1;
+
+            let line = self.line.trim();
+            if read == 0 || line.is_empty() {
+                self.done = read == 0;
+
+                if elf.items > 0 {
+                    self.index += 1;
+                    return Some(Ok(elf));
+                }
+
+                continue;
+            }
+
+            match line.parse::<usize>() {
+                Ok(calories) => {
+                    let Some(total) = elf.total.checked_add(calories) else {
+                        self.done = true;
+                        return Some(Err(anyhow!(
+                            "Line {}: elf {} carries too many calories to add up",
+                            self.line_no,
+                            elf.index
+                        )));
+                    };
+
+                    elf.total = total;
+                    elf.items += 1;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(anyhow!(
+                        "Line {}: {line:?} is not a number of calories: {err}",
+                        self.line_no
+                    )));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The `k` elves carrying the most, most first, keeping no more than `k` of
+/// them around at any time.
+pub fn top(elves: impl IntoIterator<Item = Elf>, k: usize) -> Vec<Elf> {
+    let mut heap = BinaryHeap::new();
+
+    for elf in elves {
+        heap.push(Reverse(elf));
+
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(elf)| elf)
+        .collect()
+}
+
+/// Streams the elves out of `reader`, keeping only the `k` carrying the most.
+pub fn top_from(reader: impl BufRead, k: usize) -> Result<Vec<Elf>> {
+    itertools::process_results(elves(reader), |elves| top(elves, k))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{self, BufReader, Read};
+
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+    fn elf(index: usize, total: usize, items: usize) -> Elf {
+        Elf {
+            index,
+            total,
+            items,
+        }
+    }
+
+    /// Makes up a long input as it's being read, without ever holding it all.
+    struct Synthetic {
+        elves: usize,
+        next: usize,
+        pending: Vec<u8>,
+    }
+
+    impl Read for Synthetic {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() && self.next < self.elves {
+                for item in 0..self.next % 4 + 1 {
+                    self.pending
+                        .extend(format!("{}\n", (self.next * 7919 + item) % 10000).bytes());
+                }
+                self.pending.push(b'\n');
+                self.next += 1;
+            }
+
+            let len = buf.len().min(self.pending.len());
+            buf[..len].copy_from_slice(&self.pending[..len]);
+            self.pending.drain(..len);
+
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_elves() {
+        let elves = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            elves,
+            [
+                elf(0, 6000, 3),
+                elf(1, 4000, 1),
+                elf(2, 11000, 2),
+                elf(3, 24000, 3),
+                elf(4, 10000, 1),
+            ]
+        );
+    }
+
+    #[test_case("1000\n\n\n\n2000\n" ; "several blank lines")]
+    #[test_case("\n1000\r\n\r\n2000" ; "windows line endings")]
+    fn test_elves_separators(input: &str) {
+        assert_eq!(parse(input).unwrap(), [elf(0, 1000, 1), elf(1, 2000, 1)]);
+    }
+
+    #[test]
+    fn test_not_a_number() {
+        let err = parse("1000\n\n2000\nlots\n").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Line 4: \"lots\" is not a number of calories: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_too_many_calories() {
+        let input = format!("1000\n\n{}\n1\n", usize::MAX);
+        let err = parse(&input).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Line 4: elf 1 carries too many calories to add up"
+        );
+
+        let elves = parse(&format!("{}\n\n1\n", usize::MAX)).unwrap();
+        assert!(part2(&elves, &Params { top: 2 }).is_err());
+    }
+
+    #[test_case(1, &[3] ; "top 1")]
+    #[test_case(3, &[3, 2, 4] ; "top 3")]
+    #[test_case(10, &[3, 2, 4, 0, 1] ; "more than there are")]
+    #[test_case(0, &[] ; "none")]
+    fn test_top(k: usize, expected: &[usize]) {
+        let top = top(parse(EXAMPLE).unwrap(), k);
+
+        assert_eq!(top.iter().map(|elf| elf.index).collect_vec(), expected);
+    }
+
+    #[test]
+    fn test_top_keeps_the_first_of_a_tie() {
+        let elves = parse("3000\n\n1000\n2000\n\n3000").unwrap();
+
+        assert_eq!(top(elves, 2), [elf(0, 3000, 1), elf(1, 3000, 2)]);
+    }
+
+    #[test]
+    fn test_task_1() {
+        let elves = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            part1(&elves, &Params::default()).unwrap(),
+            Answer::Integer(24000)
+        );
+    }
+
+    #[test]
+    fn test_task_2() {
+        let elves = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            part2(&elves, &Params::default()).unwrap(),
+            Answer::Integer(45000)
+        );
+        assert_eq!(
+            part2(&elves, &Params { top: 2 }).unwrap(),
+            Answer::Integer(35000)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            elf(3, 24000, 3).to_string(),
+            "elf 3: 24000 calories in 3 items"
+        );
+    }
+
+    #[test]
+    fn test_no_elves() {
+        assert!(part1(&parse("").unwrap(), &Params::default()).is_err());
+    }
+
+    #[test]
+    fn test_top_from_stream() {
+        let reader = BufReader::new(Synthetic {
+            elves: 200_000,
+            next: 0,
+            pending: Vec::new(),
+        });
+
+        let top = top_from(reader, 3).unwrap();
+
+        assert_eq!(top.len(), 3);
+        assert!(top.windows(2).all(|elves| elves[0] >= elves[1]));
+        assert!(top.iter().all(|elf| elf.items == 4));
+    }
+
+    #[test]
+    fn test_top_from_fails_on_bad_line() {
+        assert!(top_from("1000\n\nx\n".as_bytes(), 3).is_err());
+    }
+}
@@ -2,17 +2,9 @@ use std::{collections::BTreeMap, str::FromStr};
 
 use crate::prelude::*;
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day10.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day10.txt");
 
-    println!("{}", task1(input)?);
-
-    println!("{}", task2(input)?);
-
-    Ok(())
-}
-
-fn task1(input: &str) -> Result<isize> {
+pub fn parse(input: &str) -> Result<Cpu> {
     let mut cpu = Cpu::new();
 
     let signals: Vec<Signal> = input
@@ -22,23 +14,19 @@ fn task1(input: &str) -> Result<isize> {
 
     signals.iter().for_each(|s| cpu.process(s));
 
+    Ok(cpu)
+}
+
+pub fn part1(cpu: &Cpu) -> Result<Answer> {
     Ok([20, 60, 100, 140, 180, 220]
         .iter()
         .map(|v| cpu.signal(*v) * v)
-        .sum())
+        .sum::<isize>()
+        .into())
 }
 
-fn task2(input: &str) -> Result<String> {
-    let mut cpu = Cpu::new();
-
-    let signals: Vec<Signal> = input
-        .lines()
-        .map(|line| line.parse::<Signal>())
-        .collect::<Result<Vec<Signal>>>()?;
-
-    signals.iter().for_each(|s| cpu.process(s));
-
-    Ok((0..6)
+pub fn part2(cpu: &Cpu) -> Result<Answer> {
+    let image = (0..6)
         .map(|row| {
             (0..40)
                 .map(|col| {
@@ -53,16 +41,18 @@ fn task2(input: &str) -> Result<String> {
                 })
                 .join("")
         })
-        .join("\n"))
+        .join("\n");
+
+    Ok(Answer::Image(image))
 }
 
-struct Cpu {
+pub struct Cpu {
     register_history: BTreeMap<isize, isize>,
     cycle: isize,
 }
 
 impl Cpu {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             register_history: BTreeMap::from([(0, 1)]),
             cycle: 0,
@@ -71,7 +61,7 @@ impl Cpu {
 }
 
 impl Cpu {
-    fn process(&mut self, signal: &Signal) {
+    pub fn process(&mut self, signal: &Signal) {
         match signal {
             Signal::Noop => self.cycle += 1,
             Signal::AddX(x) => {
@@ -81,7 +71,7 @@ impl Cpu {
         }
     }
 
-    fn signal(&self, cycle: isize) -> isize {
+    pub fn signal(&self, cycle: isize) -> isize {
         let mut register_value: isize = 0;
 
         for (registered_cycle, change) in self.register_history.iter() {
@@ -96,8 +86,14 @@ impl Cpu {
     }
 }
 
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
-enum Signal {
+pub enum Signal {
     Noop,
     AddX(isize),
 }
@@ -124,6 +120,7 @@ impl FromStr for Signal {
 mod tests {
 
     use super::*;
+    use crate::snapshot::assert_snapshot;
 
     use test_case::test_case;
 
@@ -135,16 +132,28 @@ mod tests {
 
     #[test]
     fn test_task_1() {
-        let input = include_str!("input/day10_example.txt");
+        let input = include_str!("../../input/2022/day10_example.txt");
 
-        assert_eq!(task1(input).unwrap(), 13140);
+        assert_eq!(
+            part1(&parse(input).unwrap()).unwrap(),
+            Answer::Signed(13140)
+        );
     }
 
     #[test]
     fn test_task_2() {
-        let input = include_str!("input/day10_example.txt");
-        let expected = include_str!("input/day10_example_task2.txt");
+        let input = include_str!("../../input/2022/day10_example.txt");
+
+        let answer = part2(&parse(input).unwrap()).unwrap();
+
+        assert!(answer.is_image());
+        assert_snapshot("2022/day10_example_part2", &answer.to_string());
+    }
+
+    #[test]
+    fn test_task_2_input() {
+        let answer = part2(&parse(INPUT).unwrap()).unwrap();
 
-        assert_eq!(task2(input).unwrap(), expected)
+        assert_snapshot("2022/day10_part2", &answer.to_string());
     }
 }
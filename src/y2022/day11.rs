@@ -1,39 +1,67 @@
 use std::str::FromStr;
 
-use crate::prelude::*;
+use crate::{cancel, params, prelude::*, progress};
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day11.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day11.txt");
 
-    println!("{}", task1(input)?);
+/// How many rounds the monkeys play in each part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub part1_rounds: usize,
+    pub part2_rounds: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            part1_rounds: 20,
+            part2_rounds: 10_000,
+        }
+    }
+}
 
-    println!("{}", task2(input)?);
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "part1_rounds" => self.part1_rounds = value.parse()?,
+            "part2_rounds" => self.part2_rounds = value.parse()?,
+            _ => bail!("There is no parameter called {name}"),
+        }
 
-    Ok(())
+        Ok(())
+    }
 }
 
-enum Worry {
+pub enum Worry {
     Defo,
     Nope,
 }
 
-fn task1(input: &str) -> Result<usize> {
-    play_rounds(input, 20, Worry::Nope)
+pub fn parse(input: &str) -> Result<Vec<Monkey>> {
+    input
+        .split("\n\n")
+        .map(|chunk| chunk.parse::<Monkey>())
+        .collect::<Result<Vec<Monkey>>>()
 }
 
-fn task2(input: &str) -> Result<usize> {
-    play_rounds(input, 10000, Worry::Defo)
+pub fn part1(monkeys: &[Monkey], params: &Params) -> Result<Answer> {
+    play_rounds(monkeys, params.part1_rounds, Worry::Nope).map(Answer::from)
 }
 
-fn play_rounds(input: &str, rounds: usize, worry: Worry) -> Result<usize> {
-    let mut monkeys = input
-        .split("\n\n")
-        .map(|chunk| chunk.parse::<Monkey>())
-        .collect::<Result<Vec<Monkey>>>()?;
+pub fn part2(monkeys: &[Monkey], params: &Params) -> Result<Answer> {
+    play_rounds(monkeys, params.part2_rounds, Worry::Defo).map(Answer::from)
+}
+
+pub fn play_rounds(monkeys: &[Monkey], rounds: usize, worry: Worry) -> Result<usize> {
+    let mut monkeys = monkeys.to_vec();
 
     let mut items = monkeys.iter().map(|m| m.items.clone()).collect_vec();
     let divisor: usize = monkeys.iter().map(|m| m.division_value).product();
+    let progress = progress::start(rounds as u64);
     for _ in 0..rounds {
+        cancel::check()?;
+        progress.inc(1);
+
         for (idx, monkey) in monkeys.iter_mut().enumerate() {
             let processed_items = monkey.process_items(&items[idx], &worry, divisor);
 
@@ -52,15 +80,15 @@ fn play_rounds(input: &str, rounds: usize, worry: Worry) -> Result<usize> {
     Ok(inspect_counts.iter().rev().take(2).product())
 }
 
-#[derive(Debug, Eq, PartialEq)]
-enum Op {
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum Op {
     Mul(usize),
     Add(usize),
     Sqr,
 }
 
 impl Op {
-    fn apply(&self, rhs: usize) -> usize {
+    pub fn apply(&self, rhs: usize) -> usize {
         match self {
             Op::Mul(v) => v * rhs,
             Op::Add(v) => v + rhs,
@@ -89,14 +117,14 @@ impl FromStr for Op {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Monkey {
-    items: Vec<usize>,
-    op: Op,
-    division_value: usize,
-    happy: usize,
-    sad: usize,
-    inspect_count: usize,
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Monkey {
+    pub items: Vec<usize>,
+    pub op: Op,
+    pub division_value: usize,
+    pub happy: usize,
+    pub sad: usize,
+    pub inspect_count: usize,
 }
 
 impl FromStr for Monkey {
@@ -161,7 +189,7 @@ impl FromStr for Monkey {
 }
 
 impl Monkey {
-    fn process_item(&self, item: usize, worry: &Worry, divisor: usize) -> (usize, usize) {
+    pub fn process_item(&self, item: usize, worry: &Worry, divisor: usize) -> (usize, usize) {
         let x = {
             let tmp = self.op.apply(item);
             if matches!(worry, Worry::Nope) {
@@ -178,7 +206,7 @@ impl Monkey {
         }
     }
 
-    fn process_items(
+    pub fn process_items(
         &mut self,
         thrown_items: &[usize],
         worry: &Worry,
@@ -206,19 +234,39 @@ fn last_usize(s: &str) -> Result<usize> {
 #[cfg(test)]
 mod tests {
 
+    use std::time::Duration;
+
     use super::*;
+    use crate::cancel::CancellationToken;
 
     #[test]
     fn test_task_1() {
-        let input = include_str!("input/day11_example.txt");
+        let input = include_str!("../../input/2022/day11_example.txt");
 
-        assert_eq!(task1(input).unwrap(), 10605);
+        assert_eq!(
+            part1(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(10605)
+        );
     }
 
     #[test]
     fn test_task_2() {
-        let input = include_str!("input/day11_example.txt");
+        let input = include_str!("../../input/2022/day11_example.txt");
+
+        assert_eq!(
+            part2(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(2713310158)
+        );
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let input = include_str!("../../input/2022/day11_example.txt");
+        let monkeys = parse(input).unwrap();
+
+        let res = CancellationToken::with_timeout(Duration::ZERO)
+            .scope(|| play_rounds(&monkeys, 10000, Worry::Defo));
 
-        assert_eq!(task2(input).unwrap(), 2713310158);
+        assert!(cancel::is_cancelled(&res.unwrap_err()));
     }
 }
@@ -1,21 +1,14 @@
 use pathfinding::prelude::dijkstra;
 
 use crate::{
+    cancel,
     prelude::*,
     vector::{self, Vector2},
 };
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day12.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day12.txt");
 
-    println!("{}", task1(input)?);
-
-    println!("{}", task2(input)?);
-
-    Ok(())
-}
-
-fn task1(input: &str) -> Result<usize> {
+pub fn parse(input: &str) -> Result<Hill> {
     let height_map = input
         .lines()
         .map(|line| line.chars().map(height).collect_vec())
@@ -35,28 +28,6 @@ fn task1(input: &str) -> Result<usize> {
         .map(|idx| Vector2::from_idx(idx, width))
         .ok_or_else(|| anyhow!("Can't find end"))?;
 
-    let map = Map::new(height_map);
-
-    let result = dijkstra(
-        &start,
-        |p| map.neighbours(p).into_iter().map(|p| (p, 1)),
-        |p| *p == end,
-    )
-    .ok_or_else(|| anyhow!("Path not found"))?;
-
-    Ok(result.1)
-}
-
-fn task2(input: &str) -> Result<usize> {
-    let height_map = input
-        .lines()
-        .map(|line| line.chars().map(height).collect_vec())
-        .collect_vec();
-
-    let width = height_map[0].len();
-
-    let input = input.replace('\n', "");
-
     let starting_points = input
         .chars()
         .enumerate()
@@ -64,27 +35,48 @@ fn task2(input: &str) -> Result<usize> {
         .map(|idx| Vector2::from_idx(idx, width))
         .collect_vec();
 
-    let end = input
-        .find('E')
-        .map(|idx| Vector2::from_idx(idx, width))
-        .ok_or_else(|| anyhow!("Can't find end"))?;
+    Ok(Hill {
+        map: Map::new(height_map),
+        start,
+        end,
+        starting_points,
+    })
+}
+
+pub fn part1(hill: &Hill) -> Result<Answer> {
+    let result = dijkstra(
+        &hill.start,
+        |p| hill.map.neighbours(p).into_iter().map(|p| (p, 1_usize)),
+        |p| *p == hill.end,
+    )
+    .ok_or_else(|| anyhow!("Path not found"))?;
 
-    let map = Map::new(height_map);
+    Ok(result.1.into())
+}
 
-    let result = starting_points
-        .into_iter()
-        .filter_map(|p| {
-            dijkstra(
-                &p,
-                |p| map.neighbours(p).into_iter().map(|p| (p, 1)),
-                |p| *p == end,
-            )
+pub fn part2(hill: &Hill) -> Result<Answer> {
+    let costs = hill
+        .starting_points
+        .iter()
+        .map(|p| {
+            cancel::check()?;
+
+            Ok(dijkstra(
+                p,
+                |p| hill.map.neighbours(p).into_iter().map(|p| (p, 1_usize)),
+                |p| *p == hill.end,
+            ))
         })
+        .collect::<Result<Vec<_>>>()?;
+
+    let result = costs
+        .into_iter()
+        .flatten()
         .map(|(_, cost)| cost)
         .min()
         .ok_or_else(|| anyhow!("No minimum path found"))?;
 
-    Ok(result)
+    Ok(result.into())
 }
 
 fn height(c: char) -> usize {
@@ -95,14 +87,23 @@ fn height(c: char) -> usize {
     }
 }
 
-struct Map {
+/// The height map, along with where the climb starts and ends, and every `a`
+/// square a hiking trail could start from.
+pub struct Hill {
+    pub map: Map,
+    pub start: Vector2,
+    pub end: Vector2,
+    pub starting_points: Vec<Vector2>,
+}
+
+pub struct Map {
     height_map: Vec<Vec<usize>>,
     width: usize,
     height: usize,
 }
 
 impl Map {
-    fn new(height_map: Vec<Vec<usize>>) -> Self {
+    pub fn new(height_map: Vec<Vec<usize>>) -> Self {
         let width = height_map[0].len();
         let height = height_map.len();
         Self {
@@ -112,11 +113,11 @@ impl Map {
         }
     }
 
-    fn in_bounds(&self, pos: &Vector2) -> bool {
+    pub fn in_bounds(&self, pos: &Vector2) -> bool {
         (0..self.width as isize).contains(&pos.0) && (0..self.height as isize).contains(&pos.1)
     }
 
-    fn neighbours(&self, pos: &Vector2) -> Vec<Vector2> {
+    pub fn neighbours(&self, pos: &Vector2) -> Vec<Vector2> {
         let height = self.height_at(pos);
         [vector::NORTH, vector::EAST, vector::SOUTH, vector::WEST]
             .iter()
@@ -126,7 +127,7 @@ impl Map {
             .collect_vec()
     }
 
-    fn height_at(&self, pos: &Vector2) -> usize {
+    pub fn height_at(&self, pos: &Vector2) -> usize {
         self.height_map[pos.1 as usize][pos.0 as usize]
     }
 }
@@ -134,17 +135,30 @@ impl Map {
 #[cfg(test)]
 mod tests {
 
+    use std::time::Duration;
+
     use super::*;
+    use crate::cancel::CancellationToken;
 
     #[test]
     fn test_task1() {
-        let input = include_str!("input/day12_example.txt");
-        assert_eq!(task1(input).unwrap(), 31);
+        let input = include_str!("../../input/2022/day12_example.txt");
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), Answer::Integer(31));
     }
 
     #[test]
     fn test_task2() {
-        let input = include_str!("input/day12_example.txt");
-        assert_eq!(task2(input).unwrap(), 29);
+        let input = include_str!("../../input/2022/day12_example.txt");
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), Answer::Integer(29));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let input = include_str!("../../input/2022/day12_example.txt");
+        let hill = parse(input).unwrap();
+
+        let res = CancellationToken::with_timeout(Duration::ZERO).scope(|| part2(&hill));
+
+        assert!(cancel::is_cancelled(&res.unwrap_err()));
     }
 }
@@ -6,32 +6,19 @@ use serde_json::{json, Value};
 
 use crate::prelude::*;
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day13.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day13.txt");
 
-    println!("{}", task1(input)?);
-
-    println!("{}", task2(input)?);
-
-    Ok(())
-}
-
-fn task1(input: &str) -> Result<usize> {
-    let values: Vec<(Value, Value)> = input
+pub fn parse(input: &str) -> Result<Vec<Value>> {
+    input
         .replace("\n\n", "\n")
         .lines()
-        .chunks(2)
-        .into_iter()
-        .map(|chunk| {
-            chunk
-                .map(|c| serde_json::from_str(c).unwrap())
-                .collect_tuple()
-                .unwrap()
-        })
-        .collect_vec();
+        .map(|line| serde_json::from_str(line).map_err(|_| anyhow!("can't parse as json")))
+        .collect::<Result<Vec<Value>>>()
+}
 
-    Ok(zip(values, 1..)
-        .map(|(vals, idx)| (cmp_value(&vals.0, &vals.1), idx))
+pub fn part1(packets: &[Value]) -> Result<Answer> {
+    Ok(zip(packets.chunks_exact(2), 1..)
+        .map(|(pair, idx)| (cmp_value(&pair[0], &pair[1]), idx))
         .filter_map(|(o, idx)| {
             if matches!(o, Ordering::Less) {
                 Some(idx)
@@ -39,15 +26,12 @@ fn task1(input: &str) -> Result<usize> {
                 None
             }
         })
-        .sum())
+        .sum::<usize>()
+        .into())
 }
 
-fn task2(input: &str) -> Result<usize> {
-    let mut values: Vec<Value> = input
-        .replace("\n\n", "\n")
-        .lines()
-        .map(|line| serde_json::from_str(line).map_err(|_| anyhow!("can't parse as json")))
-        .collect::<Result<Vec<Value>>>()?;
+pub fn part2(packets: &[Value]) -> Result<Answer> {
+    let mut values = packets.to_vec();
 
     let divider1 = json!([[2]]);
     let divider2 = json!([[6]]);
@@ -60,10 +44,11 @@ fn task2(input: &str) -> Result<usize> {
     Ok(zip(values, 1..)
         .filter(|(v, _)| v == &divider1 || v == &divider2)
         .map(|(_, idx)| idx)
-        .product())
+        .product::<usize>()
+        .into())
 }
 
-fn cmp_value(v1: &Value, v2: &Value) -> Ordering {
+pub fn cmp_value(v1: &Value, v2: &Value) -> Ordering {
     match (v1, v2) {
         (Number(l), Number(r)) => l.as_i64().unwrap().cmp(&r.as_i64().unwrap()),
         (Number(l), Array(_)) => cmp_value(&json!([l.as_i64().unwrap()]), v2),
@@ -83,15 +68,15 @@ mod tests {
 
     #[test]
     fn test_task_1() {
-        let input = include_str!("input/day13_example.txt");
+        let input = include_str!("../../input/2022/day13_example.txt");
 
-        assert_eq!(task1(input).unwrap(), 13);
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), Answer::Integer(13));
     }
 
     #[test]
     fn test_task_2() {
-        let input = include_str!("input/day13_example.txt");
+        let input = include_str!("../../input/2022/day13_example.txt");
 
-        assert_eq!(task2(input).unwrap(), 140);
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), Answer::Integer(140));
     }
 }
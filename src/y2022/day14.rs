@@ -0,0 +1,576 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+};
+
+use crate::{
+    cancel, params,
+    prelude::*,
+    vector::Vector2,
+    visual::{Frame, Simulation},
+};
+
+pub const INPUT: &str = include_str!("../../input/2022/day14.txt");
+
+/// Where the sand pours in from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub source: Vector2,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            source: Vector2(500, 0),
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "source" => {
+                self.source = value
+                    .split_once(',')
+                    .ok_or_else(|| anyhow!("Not a coordinate: {value}"))?
+                    .try_into()?
+            }
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<Vector2>>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split(" -> ")
+                .map(|part| {
+                    part.split_once(',')
+                        .ok_or_else(|| anyhow!("Not a coordinate: {part}"))?
+                        .try_into()
+                })
+                .collect::<Result<Vec<Vector2>>>()
+        })
+        .collect()
+}
+
+pub fn part1(topology: &[Vec<Vector2>], params: &Params) -> Result<Answer> {
+    let mut cave = InfiniteCave::from_topology(topology, params.source);
+
+    cave.start_simulation()?;
+
+    Ok(cave
+        .map
+        .values()
+        .filter(|v| matches!(v, Thing::Sand))
+        .count()
+        .into())
+}
+
+pub fn part2(topology: &[Vec<Vector2>], params: &Params) -> Result<Answer> {
+    let mut cave = BoundedCave::from_topology(topology, params.source)?;
+
+    cave.start_simulation()?;
+
+    Ok(cave
+        .map
+        .values()
+        .filter(|v| matches!(v, Thing::Sand))
+        .count()
+        .into())
+}
+
+pub trait Cave {
+    fn is_blocked(&self, loc: Vector2) -> bool;
+    fn is_out_of_bounds(&self, loc: &Vector2) -> bool;
+
+    fn can_fall(&self, loc: &Vector2, direction: &Vector2) -> bool {
+        !self.is_blocked(loc + direction)
+    }
+
+    fn things(&self) -> &HashMap<Vector2, Thing>;
+    fn settle(&mut self, loc: Vector2);
+
+    /// Where the sand pours in from.
+    fn source(&self) -> Vector2;
+
+    /// The height of the floor, if the cave has one.
+    fn floor(&self) -> Option<isize> {
+        None
+    }
+}
+
+pub struct BoundedCave {
+    pub map: HashMap<Vector2, Thing>,
+    source: Vector2,
+    floor_y: isize,
+}
+
+impl Cave for BoundedCave {
+    fn is_blocked(&self, loc: Vector2) -> bool {
+        self.map.contains_key(&loc) || loc.1 >= self.floor_y
+    }
+
+    fn is_out_of_bounds(&self, _loc: &Vector2) -> bool {
+        false
+    }
+
+    fn things(&self) -> &HashMap<Vector2, Thing> {
+        &self.map
+    }
+
+    fn settle(&mut self, loc: Vector2) {
+        self.map.insert(loc, Thing::Sand);
+    }
+
+    fn source(&self) -> Vector2 {
+        self.source
+    }
+
+    fn floor(&self) -> Option<isize> {
+        Some(self.floor_y)
+    }
+}
+
+impl BoundedCave {
+    /// Fails if the sand would pour in at or below the floor, where it could
+    /// never come to rest.
+    pub fn from_topology(top: &[Vec<Vector2>], source: Vector2) -> Result<Self> {
+        let mut map = HashMap::new();
+
+        top.iter().for_each(|segment| {
+            segment
+                .windows(2)
+                .flat_map(|vs| vs[0].line_to(&vs[1]))
+                .for_each(|v| {
+                    map.insert(v, Thing::Rock);
+                });
+        });
+
+        let floor_y = map
+            .keys()
+            .map(|v| v.1)
+            .max()
+            .map(|v| v + 2)
+            .ok_or_else(|| anyhow!("There is no rock in the cave"))?;
+
+        if source.1 >= floor_y {
+            bail!(
+                "The sand source {},{} is not above the floor at y={floor_y}",
+                source.0,
+                source.1
+            );
+        }
+
+        Ok(Self {
+            map,
+            source,
+            floor_y,
+        })
+    }
+
+    pub fn start_simulation(&mut self) -> Result<()> {
+        loop {
+            cancel::check()?;
+
+            let sand = self.source;
+            if self.is_blocked(sand) {
+                return Ok(());
+            }
+
+            self.simulate(sand);
+        }
+    }
+
+    fn simulate(&mut self, mut sand: Vector2) -> SandResult {
+        while self.can_fall(&sand, &DOWN) {
+            sand = &sand + &DOWN;
+        }
+
+        if self.can_fall(&sand, &LEFT) {
+            return self.simulate(&sand + &LEFT);
+        } else if self.can_fall(&sand, &RIGHT) {
+            return self.simulate(&sand + &RIGHT);
+        }
+
+        self.map.insert(sand, Thing::Sand);
+
+        SandResult::Settled
+    }
+}
+
+pub struct InfiniteCave {
+    pub map: HashMap<Vector2, Thing>,
+    source: Vector2,
+    min_x: isize,
+    max_x: isize,
+    max_y: isize,
+}
+
+impl Cave for InfiniteCave {
+    fn is_blocked(&self, loc: Vector2) -> bool {
+        self.map.contains_key(&loc)
+    }
+
+    fn is_out_of_bounds(&self, loc: &Vector2) -> bool {
+        loc.0 < self.min_x || loc.0 > self.max_x || loc.1 > self.max_y
+    }
+
+    fn things(&self) -> &HashMap<Vector2, Thing> {
+        &self.map
+    }
+
+    fn settle(&mut self, loc: Vector2) {
+        self.map.insert(loc, Thing::Sand);
+    }
+
+    fn source(&self) -> Vector2 {
+        self.source
+    }
+}
+
+impl InfiniteCave {
+    pub fn from_topology(top: &[Vec<Vector2>], source: Vector2) -> Self {
+        let mut map = HashMap::new();
+
+        top.iter().for_each(|segment| {
+            segment
+                .windows(2)
+                .flat_map(|vs| vs[0].line_to(&vs[1]))
+                .for_each(|v| {
+                    map.insert(v, Thing::Rock);
+                });
+        });
+
+        let (min_x, max_x, max_y) =
+            map.keys()
+                .fold((isize::MAX, isize::MIN, isize::MIN), |mut acc, v| {
+                    if v.0 < acc.0 {
+                        // X is smaller than the minimum
+                        acc.0 = v.0
+                    }
+
+                    if v.0 > acc.1 {
+                        // X is larger than the maximum
+                        acc.1 = v.0
+                    }
+
+                    if v.1 > acc.2 {
+                        // Y is larger than the maximum
+                        acc.2 = v.1
+                    }
+
+                    acc
+                });
+
+        Self {
+            map,
+            source,
+            min_x,
+            max_x,
+            max_y,
+        }
+    }
+
+    pub fn start_simulation(&mut self) -> Result<()> {
+        loop {
+            cancel::check()?;
+
+            let sand = self.source;
+            if self.is_blocked(sand) {
+                return Ok(());
+            }
+
+            let res = self.simulate(sand);
+            if matches!(res, SandResult::Fellthrough) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn simulate(&mut self, mut sand: Vector2) -> SandResult {
+        while self.can_fall(&sand, &DOWN) {
+            if self.is_out_of_bounds(&sand) {
+                return SandResult::Fellthrough;
+            }
+
+            sand = &sand + &DOWN;
+        }
+
+        if self.can_fall(&sand, &LEFT) {
+            return self.simulate(&sand + &LEFT);
+        } else if self.can_fall(&sand, &RIGHT) {
+            return self.simulate(&sand + &RIGHT);
+        }
+
+        self.map.insert(sand, Thing::Sand);
+
+        SandResult::Settled
+    }
+}
+
+pub fn simulation(
+    topology: &[Vec<Vector2>],
+    params: &Params,
+    part: u8,
+) -> Result<Box<dyn Simulation>> {
+    Ok(match part {
+        1 => Box::new(SandSimulation::new(InfiniteCave::from_topology(
+            topology,
+            params.source,
+        ))),
+        2 => Box::new(SandSimulation::new(BoundedCave::from_topology(
+            topology,
+            params.source,
+        )?)),
+        _ => bail!("There is no part {part}"),
+    })
+}
+
+/// Drops sand into the cave one cell per step, drawing it the way the puzzle
+/// does, with the grain that's still falling as `~`.
+pub struct SandSimulation<C> {
+    cave: C,
+    falling: Option<Vector2>,
+    done: bool,
+}
+
+impl<C: Cave> SandSimulation<C> {
+    pub fn new(cave: C) -> Self {
+        Self {
+            cave,
+            falling: None,
+            done: false,
+        }
+    }
+}
+
+impl<C: Cave> Simulation for SandSimulation<C> {
+    fn step(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+
+        let Some(sand) = self.falling else {
+            if self.cave.is_blocked(self.cave.source()) {
+                self.done = true;
+                return false;
+            }
+
+            self.falling = Some(self.cave.source());
+            return true;
+        };
+
+        if self.cave.is_out_of_bounds(&sand) {
+            // the abyss takes this grain and every one after it
+            self.falling = None;
+            self.done = true;
+            return true;
+        }
+
+        self.falling = [DOWN, LEFT, RIGHT]
+            .iter()
+            .find(|direction| self.cave.can_fall(&sand, direction))
+            .map(|direction| &sand + direction);
+
+        if self.falling.is_none() {
+            self.cave.settle(sand);
+        }
+
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        let things = self.cave.things();
+        let source = self.cave.source();
+
+        let cells = || things.keys().chain([&source]).chain(&self.falling);
+        let (min_x, max_x) = cells().map(|v| v.0).minmax().into_option().unwrap();
+        let max_y = cells().map(|v| v.1).chain(self.cave.floor()).max().unwrap();
+
+        let grid = (0..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| {
+                        let cell = Vector2(x, y);
+
+                        match things.get(&cell) {
+                            _ if self.falling == Some(cell) => '~',
+                            Some(thing) => thing.char(),
+                            None if cell == source => '+',
+                            None if self.cave.floor() == Some(y) => '#',
+                            None => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let settled = things.values().filter(|v| matches!(v, Thing::Sand)).count();
+        let focus = self.falling.unwrap_or(source);
+
+        Frame {
+            title: format!("{settled} units of sand came to rest"),
+            grid,
+            focus: Some((focus.1 as usize, (focus.0 - min_x) as usize)),
+        }
+    }
+}
+
+const DOWN: Vector2 = Vector2(0, 1);
+const LEFT: Vector2 = Vector2(-1, 1);
+const RIGHT: Vector2 = Vector2(1, 1);
+
+impl Display for InfiniteCave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "min_x: {}, max_x: {}, max_y: {}",
+            self.min_x, self.max_x, self.max_y
+        )
+        .unwrap();
+
+        for y in 0..=self.max_y {
+            for x in self.min_x..=self.max_x {
+                let c = self
+                    .map
+                    .get(&Vector2(x, y))
+                    .map(|v| v.char())
+                    .unwrap_or('.');
+
+                f.write_char(c).unwrap();
+            }
+            writeln!(f).unwrap()
+        }
+
+        Ok(())
+    }
+}
+
+pub enum Thing {
+    Rock,
+    Sand,
+}
+
+impl Thing {
+    fn char(&self) -> char {
+        match self {
+            Thing::Rock => '#',
+            Thing::Sand => 'o',
+        }
+    }
+}
+
+pub enum SandResult {
+    Settled,
+    Fellthrough,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{cancel::CancellationToken, snapshot::assert_snapshot};
+
+    #[test]
+    fn test_task_1() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        assert_eq!(
+            part1(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(24)
+        );
+    }
+
+    #[test]
+    fn test_task_2() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        assert_eq!(
+            part2(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(93)
+        );
+    }
+
+    #[test]
+    fn test_source_below_the_floor() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        let params = Params {
+            source: Vector2(500, 1000),
+        };
+
+        let err = part2(&parse(input).unwrap(), &params).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The sand source 500,1000 is not above the floor at y=11"
+        );
+        assert!(simulation(&parse(input).unwrap(), &params, 2).is_err());
+        assert_eq!(
+            part1(&parse(input).unwrap(), &params).unwrap(),
+            Answer::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        let topology = parse(input).unwrap();
+
+        let res = CancellationToken::with_timeout(Duration::ZERO)
+            .scope(|| part2(&topology, &Params::default()));
+
+        assert!(cancel::is_cancelled(&res.unwrap_err()));
+    }
+
+    #[test]
+    fn test_display() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        let mut cave =
+            InfiniteCave::from_topology(&parse(input).unwrap(), Params::default().source);
+
+        cave.start_simulation().unwrap();
+
+        assert_snapshot("2022/day14_example_cave", &cave.to_string());
+    }
+
+    #[test]
+    fn test_simulation_final_frame() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        let mut simulation = simulation(&parse(input).unwrap(), &Params::default(), 1).unwrap();
+
+        while simulation.step() {}
+
+        let frame = simulation.frame();
+        let expected = vec![
+            "......+...",
+            "..........",
+            "......o...",
+            ".....ooo..",
+            "....#ooo##",
+            "...o#ooo#.",
+            "..###ooo#.",
+            "....oooo#.",
+            ".o.ooooo#.",
+            "#########.",
+        ];
+
+        assert_eq!(frame.title, "24 units of sand came to rest");
+        assert_eq!(frame.grid, expected);
+    }
+
+    #[test]
+    fn test_simulation_part_2() {
+        let input = include_str!("../../input/2022/day14_example.txt");
+        let mut simulation = simulation(&parse(input).unwrap(), &Params::default(), 2).unwrap();
+
+        assert!(simulation.step());
+        assert_eq!(simulation.frame().grid[0], "......~...");
+
+        while simulation.step() {}
+
+        assert_eq!(simulation.frame().title, "93 units of sand came to rest");
+    }
+}
@@ -0,0 +1,273 @@
+use std::{ops::RangeInclusive, str::FromStr};
+
+use crate::{cancel, params, prelude::*, progress, vector::Vector2};
+
+pub const INPUT: &str = include_str!("../../input/2022/day15.txt");
+
+/// The row to count in, and the range of both coordinates the distress beacon
+/// is somewhere in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub row: isize,
+    pub range: RangeInclusive<isize>,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: 2000000,
+            range: 1..=4000000,
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "row" => self.row = value.parse()?,
+            "range" => self.range = params::range(value)?,
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Sensor>> {
+    input.lines().map(|line| line.parse::<Sensor>()).collect()
+}
+
+pub fn part1(sensors: &[Sensor], params: &Params) -> Result<Answer> {
+    count_covered(sensors, params.row).map(Answer::from)
+}
+
+pub fn part2(sensors: &[Sensor], params: &Params) -> Result<Answer> {
+    tuning_frequency(sensors, params.range.clone()).map(Answer::from)
+}
+
+/// How many positions in the row can't hold a beacon.
+pub fn count_covered(sensors: &[Sensor], line: isize) -> Result<usize> {
+    let mut count = covered_ranges(sensors, line)
+        .into_iter()
+        .flat_map(|rs| rs.into_iter().map(|r| r.count()).collect_vec())
+        .sum();
+
+    count -= sensors
+        .iter()
+        .filter_map(|s| {
+            if s.loc.1 == line {
+                Some(s.loc.0)
+            } else if s.beacon_loc.1 == line {
+                Some(s.beacon_loc.0)
+            } else {
+                None
+            }
+        })
+        .unique()
+        .count();
+
+    Ok(count)
+}
+
+use rayon::prelude::*;
+
+/// How many rows are scanned between reporting progress and checking whether
+/// the task was cancelled.
+const ROWS_PER_TICK: isize = 4096;
+
+/// Finds the only position within `range` that no sensor covers.
+pub fn tuning_frequency(sensors: &[Sensor], range: RangeInclusive<isize>) -> Result<isize> {
+    // the rows are scanned on rayon's threads, which don't know the task
+    let token = cancel::current();
+    let progress = progress::start(range.clone().count() as u64);
+
+    let found = range
+        .into_par_iter()
+        .filter_map(|y| {
+            // counting every row would have the threads fight over the counter,
+            // and checking the deadline on every row would read the clock as often
+            if y % ROWS_PER_TICK == 0 {
+                if let Err(err) = token.check() {
+                    return Some(Err(err));
+                }
+
+                progress.inc(ROWS_PER_TICK as u64);
+            }
+
+            if let Some(ranges) = covered_ranges(sensors, y) {
+                if ranges.len() > 1 {
+                    let first = ranges.first().unwrap();
+                    let x = first.clone().last().unwrap() + 1;
+                    Some(Ok((x, y)))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        })
+        .collect::<Result<Vec<(isize, isize)>>>()?;
+
+    if let Some((x, y)) = found.first() {
+        Ok(x * 4000000 + y)
+    } else {
+        bail!("Did not find");
+    }
+}
+
+pub fn covered_ranges(sensors: &[Sensor], line: isize) -> Option<Vec<RangeInclusive<isize>>> {
+    let sensors_in_range = sensors.iter().filter(|s| s.in_range(line)).collect_vec();
+
+    if sensors_in_range.is_empty() {
+        return None;
+    }
+
+    let mut ranges = sensors_in_range
+        .iter()
+        .map(|sensor| {
+            let num_covered = sensor.range - (sensor.loc.1 - line).abs();
+
+            sensor.loc.0 - num_covered..=sensor.loc.0 + num_covered
+        })
+        .collect_vec();
+
+    ranges.sort_by(|a, b| a.start().cmp(b.start()));
+
+    let mut row_ranges: Vec<RangeInclusive<isize>> = Vec::new();
+    let mut current_range = ranges[0].clone();
+
+    for range in ranges.iter().skip(1) {
+        if current_range.start() <= range.start() && current_range.end() >= range.end() {
+            continue;
+        }
+
+        if range.start() - 1 <= *current_range.end() {
+            current_range = *current_range.start()..=*range.end();
+            continue;
+        }
+
+        row_ranges.push(current_range);
+        current_range = range.clone();
+    }
+
+    if row_ranges.is_empty() || row_ranges.last().unwrap() != &current_range {
+        row_ranges.push(current_range);
+    }
+
+    Some(row_ranges)
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Sensor {
+    pub loc: Vector2,
+    pub beacon_loc: Vector2,
+    pub range: isize,
+}
+
+impl Sensor {
+    pub fn in_range(&self, row: isize) -> bool {
+        (self.loc.1 - row).abs() <= self.range
+    }
+}
+
+impl FromStr for Sensor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (x, y, beacon_x, beacon_y) = scan!(
+            s,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            isize,
+            isize,
+            isize,
+            isize
+        )?;
+
+        let loc = Vector2(x, y);
+        let beacon_loc = Vector2(beacon_x, beacon_y);
+
+        Ok(Self {
+            range: loc.manhattan_distance(&beacon_loc),
+            loc,
+            beacon_loc,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cancel::CancellationToken;
+
+    #[test]
+    fn parse_sensor() {
+        let input = "Sensor at x=12, y=14: closest beacon is at x=10, y=16";
+
+        let sensor = input.parse::<Sensor>().unwrap();
+        let expected = Sensor {
+            loc: Vector2(12, 14),
+            beacon_loc: Vector2(10, 16),
+            range: 4,
+        };
+
+        assert_eq!(sensor, expected);
+    }
+
+    #[test]
+    fn sensor_in_range() {
+        let s = Sensor {
+            loc: Vector2(10, 10),
+            beacon_loc: Vector2(10, 20),
+            range: 10,
+        };
+
+        (0..=20).for_each(|row| assert!(s.in_range(row), "row {row}"));
+
+        (21..30).for_each(|row| assert!(!s.in_range(row), "row {row}"));
+        (-10..0).for_each(|row| assert!(!s.in_range(row), "row {row}"));
+    }
+
+    #[test]
+    fn test_task_1() {
+        let input = include_str!("../../input/2022/day15_example.txt");
+
+        assert_eq!(count_covered(&parse(input).unwrap(), 10).unwrap(), 26);
+    }
+
+    #[test]
+    fn test_task_2() {
+        let input = include_str!("../../input/2022/day15_example.txt");
+
+        assert_eq!(
+            tuning_frequency(&parse(input).unwrap(), 1..=20).unwrap(),
+            56000011
+        );
+    }
+
+    #[test]
+    fn test_example_params() {
+        let input = include_str!("../../input/2022/day15_example.txt");
+        let sensors = parse(input).unwrap();
+        let params = Params {
+            row: 10,
+            range: 0..=20,
+        };
+
+        assert_eq!(part1(&sensors, &params).unwrap(), Answer::Integer(26));
+        assert_eq!(part2(&sensors, &params).unwrap(), Answer::Signed(56000011));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let input = include_str!("../../input/2022/day15_example.txt");
+        let sensors = parse(input).unwrap();
+
+        let res = CancellationToken::with_timeout(Duration::ZERO)
+            .scope(|| tuning_frequency(&sensors, 0..=20));
+
+        assert!(cancel::is_cancelled(&res.unwrap_err()));
+    }
+}
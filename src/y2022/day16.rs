@@ -0,0 +1,315 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    str::FromStr,
+};
+
+use crate::{cancel, params, prelude::*};
+
+pub const INPUT: &str = include_str!("../../input/2022/day16.txt");
+
+/// The valve to start at, how many minutes there are until the volcano erupts,
+/// and how many of those it takes to teach the elephant how to help.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub start: String,
+    pub minutes: isize,
+    pub teaching: isize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            start: "AA".to_string(),
+            minutes: 30,
+            teaching: 4,
+        }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "start" => self.start = value.to_string(),
+            "minutes" => self.minutes = value.parse()?,
+            "teaching" => self.teaching = value.parse()?,
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Cave> {
+    let valves = input
+        .lines()
+        .map(|line| line.parse::<Valve>())
+        .map_ok(|v| (v.name.clone(), v))
+        .collect::<Result<_>>()?;
+
+    Ok(Cave::from(valves))
+}
+
+pub fn part1(cave: &Cave, params: &Params) -> Result<Answer> {
+    cave.play(&params.start, params.minutes).map(Answer::from)
+}
+
+pub fn part2(cave: &Cave, params: &Params) -> Result<Answer> {
+    cave.play_with_elephant(&params.start, params.minutes - params.teaching)
+        .map(Answer::from)
+}
+
+#[derive(Debug)]
+pub struct Cave {
+    valves: HashMap<String, Valve>,
+    distances: HashMap<(String, String), isize>,
+}
+
+impl Cave {
+    pub fn from(valves: HashMap<String, Valve>) -> Self {
+        let distances = valves
+            .keys()
+            .flat_map(|from| {
+                distances_from(&valves, from)
+                    .into_iter()
+                    .map(move |(to, d)| ((from.clone(), to), d))
+            })
+            .collect();
+
+        Self { valves, distances }
+    }
+
+    /// Tries every order of opening the valves that are worth opening, and
+    /// returns the most pressure that can be relieved in the given time.
+    pub fn play(&self, start: &str, minutes: isize) -> Result<isize> {
+        let relief = self.relief_per_opened(start, minutes)?;
+
+        Ok(relief.into_values().max().unwrap_or_default())
+    }
+
+    /// Same as [`Cave::play`], except an elephant opens valves alongside us.
+    /// Neither of us opens a valve the other one has already opened, so the
+    /// best we can do is the best pair of disjoint sets of valves.
+    pub fn play_with_elephant(&self, start: &str, minutes: isize) -> Result<isize> {
+        let relief = self.relief_per_opened(start, minutes)?;
+
+        Ok(relief
+            .iter()
+            .tuple_combinations()
+            .filter(|((ours, _), (theirs, _))| *ours & *theirs == 0)
+            .map(|((_, ours), (_, theirs))| ours + theirs)
+            .max()
+            .unwrap_or_default())
+    }
+
+    /// The most pressure that can be relieved for every set of opened valves,
+    /// where a set is a bitmask over the valves with a non-zero flow rate.
+    pub fn relief_per_opened(&self, start: &str, minutes: isize) -> Result<HashMap<u64, isize>> {
+        let useful = self
+            .valves
+            .values()
+            .filter(|v| v.flow_rate != 0)
+            .collect_vec();
+
+        if useful.len() > u64::BITS as usize {
+            bail!("Too many valves worth opening: {}", useful.len());
+        }
+
+        let mut relief = HashMap::new();
+        self.explore(start, minutes, 0, 0, &useful, &mut relief)?;
+
+        Ok(relief)
+    }
+
+    fn explore(
+        &self,
+        location: &str,
+        remaining_minutes: isize,
+        open_valves: u64,
+        relieved_pressure: isize,
+        useful: &[&Valve],
+        relief: &mut HashMap<u64, isize>,
+    ) -> Result<()> {
+        cancel::check()?;
+
+        let best = relief.entry(open_valves).or_default();
+        *best = (*best).max(relieved_pressure);
+
+        for (idx, valve) in useful.iter().enumerate() {
+            if open_valves & (1 << idx) != 0 {
+                continue;
+            }
+
+            // a valve that can't be reached is never opened
+            let Ok(distance) = self.distance(location, &valve.name) else {
+                continue;
+            };
+
+            // walking there, plus a minute to open it
+            let remaining_minutes = remaining_minutes - distance - 1;
+            if remaining_minutes <= 0 {
+                continue;
+            }
+
+            self.explore(
+                &valve.name,
+                remaining_minutes,
+                open_valves | (1 << idx),
+                relieved_pressure + remaining_minutes * valve.flow_rate,
+                useful,
+                relief,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn distance(&self, from: &str, to: &str) -> Result<isize> {
+        self.distances
+            .get(&(from.to_string(), to.to_string()))
+            .copied()
+            .ok_or_else(|| anyhow!("No path between {from} -> {to}"))
+    }
+}
+
+/// Breadth-first walk through the tunnels, returning how many minutes it takes
+/// to get from `from` to every reachable valve.
+fn distances_from(valves: &HashMap<String, Valve>, from: &str) -> HashMap<String, isize> {
+    let mut distances = HashMap::from([(from.to_string(), 0)]);
+    let mut queue = VecDeque::from([from]);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = distances[current];
+
+        let Some(valve) = valves.get(current) else {
+            continue;
+        };
+
+        for c in valve.connections.iter() {
+            if !distances.contains_key(c) {
+                distances.insert(c.clone(), distance + 1);
+                queue.push_back(c);
+            }
+        }
+    }
+
+    distances
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Valve {
+    pub name: String,
+    pub flow_rate: isize,
+    pub connections: Vec<String>,
+}
+
+impl FromStr for Valve {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, flow_rate, connections) = scan!(
+            s,
+            "Valve {} has flow rate={}; tunnel{s?} lead{s?} to valve{s?} {}",
+            String,
+            isize,
+            String
+        )?;
+
+        let connections = connections.split(", ").map(String::from).collect_vec();
+
+        Ok(Self {
+            name,
+            flow_rate,
+            connections,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::time::Duration;
+
+    use super::*;
+    use crate::cancel::CancellationToken;
+    use test_case::test_case;
+
+    #[test_case("Valve AA has flow rate=0; tunnels lead to valves DD, II, BB", Valve { name: "AA".to_string(), flow_rate: 0, connections: vec!["DD".to_string(), "II".to_string(), "BB".to_string()]})]
+    #[test_case("Valve HH has flow rate=22; tunnel leads to valve GG", Valve { name: "HH".to_string(), flow_rate: 22, connections: vec!["GG".to_string()]})]
+    fn test_parse_valve(input: &str, expected: Valve) {
+        assert_eq!(input.parse::<Valve>().unwrap(), expected);
+    }
+
+    #[test_case("AA", "AA", 0)]
+    #[test_case("AA", "DD", 1)]
+    #[test_case("AA", "JJ", 2)]
+    #[test_case("AA", "HH", 5)]
+    #[test_case("HH", "JJ", 7)]
+    fn test_distance(from: &str, to: &str, expected: isize) {
+        let cave = parse(include_str!("../../input/2022/day16_example.txt")).unwrap();
+
+        assert_eq!(cave.distance(from, to).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_task_1() {
+        let input = include_str!("../../input/2022/day16_example.txt");
+
+        assert_eq!(
+            part1(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Signed(1651)
+        )
+    }
+
+    #[test]
+    fn test_task_2() {
+        let input = include_str!("../../input/2022/day16_example.txt");
+
+        assert_eq!(
+            part2(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Signed(1707)
+        )
+    }
+
+    #[test_case(4, 1707 ; "default")]
+    #[test_case(30, 0 ; "no time left")]
+    fn test_teaching(teaching: isize, expected: i64) {
+        let cave = parse(include_str!("../../input/2022/day16_example.txt")).unwrap();
+        let params = Params {
+            teaching,
+            ..Params::default()
+        };
+
+        assert_eq!(part2(&cave, &params).unwrap(), Answer::Signed(expected));
+    }
+
+    #[test]
+    fn test_unreachable_valve() {
+        let input = "Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5; tunnel leads to valve AA
+Valve CC has flow rate=20; tunnel leads to valve CC";
+        let cave = parse(input).unwrap();
+
+        assert_eq!(cave.play("AA", 30).unwrap(), 5 * 28);
+    }
+
+    #[test]
+    fn test_relief_per_opened() {
+        let cave = parse(include_str!("../../input/2022/day16_example.txt")).unwrap();
+        let relief = cave.relief_per_opened("AA", 30).unwrap();
+
+        // opening nothing relieves nothing, and the best of them is part 1
+        assert_eq!(relief[&0], 0);
+        assert_eq!(relief.values().max(), Some(&1651));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let input = include_str!("../../input/2022/day16_example.txt");
+        let cave = parse(input).unwrap();
+
+        let res = CancellationToken::with_timeout(Duration::ZERO)
+            .scope(|| cave.play_with_elephant("AA", 26));
+
+        assert!(cancel::is_cancelled(&res.unwrap_err()));
+    }
+}
@@ -0,0 +1,383 @@
+use std::fmt::Display;
+
+use crate::{params, prelude::*};
+
+pub const INPUT: &str = include_str!("../../input/2022/day2.txt");
+
+/// The puzzle's parameters are the rules of the game.
+pub type Params = Game;
+
+pub fn parse(input: &str) -> Result<Vec<(char, char)>> {
+    input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .collect_tuple::<(char, char, char)>()
+                .filter(|(_, space, _)| *space == ' ')
+                .map(|(first, _, second)| (first, second))
+                .ok_or_else(|| anyhow!("What kinda input is that: {line}"))
+        })
+        .collect()
+}
+
+pub fn part1(guide: &[(char, char)], game: &Game) -> Result<Answer> {
+    game.play(guide, Strategy::Shapes)
+        .map(|tally| tally.score.into())
+}
+
+pub fn part2(guide: &[(char, char)], game: &Game) -> Result<Answer> {
+    game.play(guide, Strategy::Outcomes)
+        .map(|tally| tally.score.into())
+}
+
+/// What the second column of the strategy guide says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The shape to play.
+    Shapes,
+    /// How the round needs to end.
+    Outcomes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+/// A cyclic game of an odd number of shapes, such as rock paper scissors or
+/// rock paper scissors lizard Spock. Going round the cycle, every shape beats
+/// the half of the others that come right before it, and loses to the half
+/// that come right after it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    /// The names of the shapes, in the order of the cycle.
+    pub shapes: Vec<String>,
+    /// What playing each shape scores.
+    pub shape_scores: Vec<usize>,
+    /// What a loss, a draw and a win score, in that order.
+    pub outcome_scores: [usize; 3],
+    /// The letters the strategy guide uses for each of their shapes.
+    pub theirs: Vec<char>,
+    /// The letters the strategy guide uses for each of our shapes.
+    pub ours: Vec<char>,
+    /// The letters the strategy guide uses to ask for a loss, a draw and a win.
+    pub outcomes: [char; 3],
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self {
+            shapes: vec!["rock".into(), "paper".into(), "scissors".into()],
+            shape_scores: vec![1, 2, 3],
+            outcome_scores: [0, 3, 6],
+            theirs: vec!['A', 'B', 'C'],
+            ours: vec!['X', 'Y', 'Z'],
+            outcomes: ['X', 'Y', 'Z'],
+        }
+    }
+}
+
+impl params::Params for Game {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let list = || value.split(',').map(str::trim);
+        let letters = || value.chars().filter(|c| !c.is_whitespace() && *c != ',');
+
+        match name {
+            "shapes" => self.shapes = list().map(str::to_string).collect(),
+            "shape_scores" => self.shape_scores = list().map(str::parse).try_collect()?,
+            "outcome_scores" => {
+                self.outcome_scores = list()
+                    .map(str::parse)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .try_into()
+                    .map_err(|_| anyhow!("Expected the scores of a loss, a draw and a win"))?
+            }
+            "theirs" => self.theirs = letters().collect(),
+            "ours" => self.ours = letters().collect(),
+            "outcomes" => {
+                self.outcomes = letters()
+                    .collect_vec()
+                    .try_into()
+                    .map_err(|_| anyhow!("Expected the letters of a loss, a draw and a win"))?
+            }
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Rock paper scissors lizard Spock, scoring its shapes 1 to 5 in the order
+    /// of the cycle, with their letters running from `A` to `E` and ours from
+    /// `V` to `Z`.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Self {
+            shapes: ["rock", "Spock", "paper", "lizard", "scissors"]
+                .map(String::from)
+                .to_vec(),
+            shape_scores: vec![1, 2, 3, 4, 5],
+            theirs: vec!['A', 'B', 'C', 'D', 'E'],
+            ours: vec!['V', 'W', 'X', 'Y', 'Z'],
+            ..Self::default()
+        }
+    }
+
+    /// Checks the game can be played: an odd number of shapes, each with a
+    /// score and a letter on both sides, and distinct letters.
+    pub fn validate(&self) -> Result<()> {
+        let n = self.shapes.len();
+
+        if n.is_multiple_of(2) {
+            bail!("A cyclic game needs an odd number of shapes, not {n}");
+        }
+
+        for (what, len) in [
+            ("shape scores", self.shape_scores.len()),
+            ("letters for their shapes", self.theirs.len()),
+            ("letters for our shapes", self.ours.len()),
+        ] {
+            if len != n {
+                bail!("Expected {n} {what}, got {len}");
+            }
+        }
+
+        for (what, letters) in [
+            ("their shapes", &self.theirs[..]),
+            ("our shapes", &self.ours[..]),
+            ("the outcomes", &self.outcomes[..]),
+        ] {
+            if !letters.iter().all_unique() {
+                bail!("The letters for {what} are not distinct: {letters:?}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// How the round goes for us.
+    pub fn outcome(&self, theirs: usize, ours: usize) -> Outcome {
+        let n = self.shapes.len();
+
+        match (ours + n - theirs) % n {
+            0 => Outcome::Draw,
+            d if d <= n / 2 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    /// A shape for us that ends the round the way it needs to. When several
+    /// shapes would, it's the closest one in the cycle.
+    pub fn shape_for(&self, theirs: usize, outcome: Outcome) -> usize {
+        let n = self.shapes.len();
+
+        match outcome {
+            Outcome::Loss => (theirs + n - 1) % n,
+            Outcome::Draw => theirs,
+            Outcome::Win => (theirs + 1) % n,
+        }
+    }
+
+    /// What a single round scores for us.
+    pub fn score(&self, ours: usize, outcome: Outcome) -> usize {
+        self.shape_scores[ours] + self.outcome_scores[outcome as usize]
+    }
+
+    /// Plays every round of the guide, reading its second column the way the
+    /// strategy says to.
+    pub fn play(&self, guide: &[(char, char)], strategy: Strategy) -> Result<Tally> {
+        self.validate()?;
+
+        guide
+            .iter()
+            .try_fold(Tally::default(), |mut tally, &(them, us)| {
+                let theirs = letter(&self.theirs, them)?;
+                let ours = match strategy {
+                    Strategy::Shapes => letter(&self.ours, us)?,
+                    Strategy::Outcomes => {
+                        let outcome = [Outcome::Loss, Outcome::Draw, Outcome::Win]
+                            [letter(&self.outcomes, us)?];
+
+                        self.shape_for(theirs, outcome)
+                    }
+                };
+
+                let outcome = self.outcome(theirs, ours);
+                tally.add(outcome, self.score(ours, outcome));
+
+                Ok(tally)
+            })
+    }
+}
+
+fn letter(letters: &[char], letter: char) -> Result<usize> {
+    letters
+        .iter()
+        .position(|l| *l == letter)
+        .ok_or_else(|| anyhow!("{letter:?} is not one of {letters:?}"))
+}
+
+/// How a whole game went, round by round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: usize,
+}
+
+impl Tally {
+    pub fn add(&mut self, outcome: Outcome, score: usize) {
+        match outcome {
+            Outcome::Loss => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Win => self.wins += 1,
+        }
+
+        self.score += score;
+    }
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} wins, {} draws and {} losses, scoring {}",
+            self.wins, self.draws, self.losses, self.score
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::params::Params as _;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "A Y\nB X\nC Z";
+
+    fn rpsls(shape: &str) -> usize {
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        game.shapes.iter().position(|s| s == shape).unwrap()
+    }
+
+    #[test]
+    fn test_task_1() {
+        let guide = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            part1(&guide, &Game::default()).unwrap(),
+            Answer::Integer(15)
+        );
+    }
+
+    #[test]
+    fn test_task_2() {
+        let guide = parse(EXAMPLE).unwrap();
+
+        assert_eq!(
+            part2(&guide, &Game::default()).unwrap(),
+            Answer::Integer(12)
+        );
+    }
+
+    #[test_case(Strategy::Shapes, Tally { wins: 1, draws: 1, losses: 1, score: 15 } ; "shapes")]
+    #[test_case(Strategy::Outcomes, Tally { wins: 1, draws: 1, losses: 1, score: 12 } ; "outcomes")]
+    fn test_tally(strategy: Strategy, expected: Tally) {
+        let guide = parse(EXAMPLE).unwrap();
+
+        assert_eq!(Game::default().play(&guide, strategy).unwrap(), expected);
+    }
+
+    #[test_case("rock", "scissors", Outcome::Win)]
+    #[test_case("rock", "lizard", Outcome::Win)]
+    #[test_case("rock", "paper", Outcome::Loss)]
+    #[test_case("rock", "Spock", Outcome::Loss)]
+    #[test_case("Spock", "scissors", Outcome::Win)]
+    #[test_case("lizard", "Spock", Outcome::Win)]
+    #[test_case("lizard", "paper", Outcome::Win)]
+    #[test_case("paper", "Spock", Outcome::Win)]
+    #[test_case("scissors", "lizard", Outcome::Win)]
+    #[test_case("lizard", "lizard", Outcome::Draw)]
+    fn test_rock_paper_scissors_lizard_spock(ours: &str, theirs: &str, expected: Outcome) {
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        assert_eq!(game.outcome(rpsls(theirs), rpsls(ours)), expected);
+    }
+
+    #[test]
+    fn test_shape_for() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+
+        for theirs in 0..5 {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                let ours = game.shape_for(theirs, outcome);
+
+                assert_eq!(game.outcome(theirs, ours), outcome);
+            }
+        }
+    }
+
+    #[test]
+    fn test_configured_game() {
+        let mut game = Game::default();
+        game.set("outcome_scores", "1, 2, 4").unwrap();
+        game.set("theirs", "R,P,S").unwrap();
+        game.set("ours", "rps").unwrap();
+
+        let guide = parse("R p\nP r\nS s").unwrap();
+
+        assert_eq!(
+            game.play(&guide, Strategy::Shapes).unwrap(),
+            Tally {
+                wins: 1,
+                draws: 1,
+                losses: 1,
+                score: 2 + 4 + 1 + 1 + 3 + 2,
+            }
+        );
+    }
+
+    #[test_case("shapes", "rock,paper" ; "even")]
+    #[test_case("shape_scores", "1,2" ; "missing score")]
+    #[test_case("ours", "XYX" ; "repeated letter")]
+    fn test_invalid_game(name: &str, value: &str) {
+        let mut game = Game::default();
+        game.set(name, value).unwrap();
+
+        assert!(game.validate().is_err());
+        assert!(game.play(&[], Strategy::Shapes).is_err());
+    }
+
+    #[test]
+    fn test_unknown_letter() {
+        let guide = parse("A Y\nD X").unwrap();
+
+        let err = Game::default().play(&guide, Strategy::Shapes).unwrap_err();
+
+        assert_eq!(err.to_string(), "'D' is not one of ['A', 'B', 'C']");
+    }
+
+    #[test_case("A" ; "one column")]
+    #[test_case("A  Y" ; "two spaces")]
+    #[test_case("AY" ; "no space")]
+    fn test_parse_invalid(line: &str) {
+        assert!(parse(line).is_err());
+    }
+
+    #[test]
+    fn test_display_tally() {
+        let tally = Game::default()
+            .play(&parse(EXAMPLE).unwrap(), Strategy::Outcomes)
+            .unwrap();
+
+        assert_eq!(
+            tally.to_string(),
+            "1 wins, 1 draws and 1 losses, scoring 12"
+        );
+    }
+}
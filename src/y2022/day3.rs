@@ -0,0 +1,335 @@
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOr},
+};
+
+use crate::{params, prelude::*};
+
+pub const INPUT: &str = include_str!("../../input/2022/day3.txt");
+
+/// How many elves there are to a group, each group carrying the same badge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub group: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { group: 3 }
+    }
+}
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "group" => self.group = value.parse()?,
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Rucksack>> {
+    input.lines().map(str::parse).collect()
+}
+
+pub fn part1(rucksacks: &[Rucksack], _params: &Params) -> Result<Answer> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(idx, rucksack)| {
+            let [first, second] = rucksack.compartments;
+
+            (first & second)
+                .only()
+                .with_context(|| format!("Rucksack {}", idx + 1))
+        })
+        .sum::<Result<usize>>()
+        .map(Answer::from)
+}
+
+pub fn part2(rucksacks: &[Rucksack], params: &Params) -> Result<Answer> {
+    if params.group == 0 || !rucksacks.len().is_multiple_of(params.group) {
+        bail!(
+            "{} rucksacks can't be split into groups of {}",
+            rucksacks.len(),
+            params.group
+        );
+    }
+
+    rucksacks
+        .chunks(params.group)
+        .enumerate()
+        .map(|(idx, group)| {
+            group
+                .iter()
+                .map(Rucksack::items)
+                .fold(ItemSet::ALL, |common, items| common & items)
+                .only()
+                .with_context(|| format!("Group {}", idx + 1))
+        })
+        .sum::<Result<usize>>()
+        .map(Answer::from)
+}
+
+/// A rucksack's items, split between its two compartments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    pub compartments: [ItemSet; 2],
+}
+
+impl Rucksack {
+    /// Everything in the rucksack, whichever compartment it's in.
+    pub fn items(&self) -> ItemSet {
+        self.compartments[0] | self.compartments[1]
+    }
+}
+
+impl std::str::FromStr for Rucksack {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // items are all letters, so splitting by bytes splits by items
+        if let Some(item) = s.chars().find(|c| !c.is_ascii()) {
+            bail!("{item} in {s} is not an item");
+        }
+
+        if !s.len().is_multiple_of(2) {
+            bail!("The compartments of {s} aren't the same size");
+        }
+
+        let (first, second) = s.split_at(s.len() / 2);
+
+        Ok(Self {
+            compartments: [first.parse()?, second.parse()?],
+        })
+    }
+}
+
+/// A set of item types, one bit for each priority from 1 to 52, so taking
+/// intersections and unions never allocates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: Self = Self(0);
+    /// Every item type there is.
+    pub const ALL: Self = Self(((1 << 52) - 1) << 1);
+
+    pub fn insert(&mut self, item: char) -> Result<()> {
+        self.0 |= 1 << priority(item)?;
+
+        Ok(())
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        priority(item).is_ok_and(|p| self.0 & (1 << p) != 0)
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The priorities of the items in the set, lowest first.
+    pub fn priorities(&self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let priority = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+
+                priority
+            })
+        })
+    }
+
+    /// The items in the set, in order of priority.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        self.priorities().map(item)
+    }
+
+    /// The priority of the only item in the set, failing if there's none or
+    /// more than one.
+    pub fn only(&self) -> Result<usize> {
+        match self.len() {
+            1 => Ok(self.0.trailing_zeros() as usize),
+            0 => bail!("No item in common"),
+            _ => bail!("Several items in common: {self}"),
+        }
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl std::str::FromStr for ItemSet {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.chars().try_fold(Self::EMPTY, |mut set, item| {
+            set.insert(item)?;
+
+            Ok(set)
+        })
+    }
+}
+
+impl Display for ItemSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.items().join(", "))
+    }
+}
+
+/// `a` to `z` come first, from 1 to 26, and then `A` to `Z`, from 27 to 52.
+pub fn priority(item: char) -> Result<usize> {
+    match item {
+        'a'..='z' => Ok(item as usize - 'a' as usize + 1),
+        'A'..='Z' => Ok(item as usize - 'A' as usize + 27),
+        _ => bail!("{item:?} is not an item"),
+    }
+}
+
+fn item(priority: usize) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
+
+    #[test]
+    fn task_1_example_test() {
+        assert_eq!(
+            part1(&parse(EXAMPLE).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(157)
+        )
+    }
+
+    #[test]
+    fn task_2_example_test() {
+        assert_eq!(
+            part2(&parse(EXAMPLE).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(70)
+        )
+    }
+
+    #[test]
+    fn test_score() {
+        ('a'..='z').enumerate().for_each(|(idx, c)| {
+            assert_eq!(priority(c).unwrap(), idx + 1);
+            assert_eq!(item(idx + 1), c);
+        });
+
+        ('A'..='Z').enumerate().for_each(|(idx, c)| {
+            assert_eq!(priority(c).unwrap(), idx + 27);
+            assert_eq!(item(idx + 27), c);
+        });
+
+        assert!(priority('1').is_err());
+    }
+
+    #[test]
+    fn test_item_set() {
+        let first: ItemSet = "vJrwpWtwJgWr".parse().unwrap();
+        let second: ItemSet = "hcsFMMfFFhFp".parse().unwrap();
+
+        assert_eq!(first.len(), 8);
+        assert!(first.contains('J') && !first.contains('h'));
+        assert_eq!((first & second).items().collect_vec(), ['p']);
+        assert_eq!((first | second).len(), 14);
+        assert_eq!(
+            (first | second).to_string(),
+            "c, f, g, h, p, r, s, t, v, w, F, J, M, W"
+        );
+    }
+
+    #[test]
+    fn test_all() {
+        let all = ('a'..='z')
+            .chain('A'..='Z')
+            .collect::<String>()
+            .parse::<ItemSet>()
+            .unwrap();
+
+        assert_eq!(all, ItemSet::ALL);
+        assert_eq!(
+            ItemSet::ALL.priorities().collect_vec(),
+            (1..=52).collect_vec()
+        );
+    }
+
+    #[test_case("ab", "cd", "No item in common" ; "none")]
+    #[test_case("aBc", "Bda", "Several items in common: a, B" ; "several")]
+    fn test_only(first: &str, second: &str, expected: &str) {
+        let set = first.parse::<ItemSet>().unwrap() & second.parse::<ItemSet>().unwrap();
+
+        assert_eq!(set.only().unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_part1_errors() {
+        let rucksacks = parse("abcd\naBaB").unwrap();
+
+        let err = part1(&rucksacks, &Params::default()).unwrap_err();
+
+        assert_eq!(format!("{err:#}"), "Rucksack 1: No item in common");
+    }
+
+    #[test]
+    fn test_pairs() {
+        let rucksacks = parse("abcd\naxyz\nBcdE\nBqrs").unwrap();
+
+        assert_eq!(
+            part2(&rucksacks, &Params { group: 2 }).unwrap(),
+            Answer::Integer(1 + 28)
+        );
+    }
+
+    #[test_case(4 ; "uneven")]
+    #[test_case(0 ; "empty groups")]
+    fn test_invalid_group_size(group: usize) {
+        let rucksacks = parse(EXAMPLE).unwrap();
+
+        assert!(part2(&rucksacks, &Params { group }).is_err());
+    }
+
+    #[test_case("abc" ; "odd size")]
+    #[test_case("ab1c" ; "not an item")]
+    #[test_case("aéa" ; "not ascii")]
+    fn test_parse_invalid(rucksack: &str) {
+        assert!(parse(rucksack).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::{Display, Write},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+use crate::prelude::*;
+
+pub const INPUT: &str = include_str!("../../input/2022/day4.txt");
+
+pub fn parse(input: &str) -> Result<Vec<SectionsPair>> {
+    input
+        .lines()
+        .map(|line| line.parse::<SectionsPair>())
+        .collect()
+}
+
+pub fn part1(pairs: &[SectionsPair]) -> Result<Answer> {
+    Ok(pairs
+        .iter()
+        .filter(|s| s.is_subset_sections())
+        .count()
+        .into())
+}
+
+pub fn part2(pairs: &[SectionsPair]) -> Result<Answer> {
+    Ok(pairs
+        .iter()
+        .filter(|s| s.is_overlapping_sections())
+        .count()
+        .into())
+}
+
+pub struct SectionsPair {
+    pub left: Sections,
+    pub right: Sections,
+}
+
+impl FromStr for SectionsPair {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (left, right) = s.split_once(',').context("No comma found")?;
+
+        let left = left.parse::<Sections>()?;
+        let right = right.parse::<Sections>()?;
+
+        Ok(SectionsPair { left, right })
+    }
+}
+
+impl SectionsPair {
+    pub fn is_overlapping_sections(&self) -> bool {
+        self.left.overlaps(&self.right) || self.right.overlaps(&self.left)
+    }
+
+    pub fn is_subset_sections(&self) -> bool {
+        self.left.contains(&self.right) || self.right.contains(&self.left)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sections {
+    pub range: RangeInclusive<usize>,
+}
+
+impl FromStr for Sections {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        s.split_once('-')
+            .map(|(min, max)| {
+                let min = min.parse()?;
+                let max = max.parse()?;
+
+                Ok(Self { range: (min..=max) })
+            })
+            .context(format!("Failed parsing section: {s}"))?
+    }
+}
+
+impl Sections {
+    pub fn contains(&self, other: &Sections) -> bool {
+        self.range.contains(other.range.start()) && self.range.contains(other.range.end())
+    }
+
+    pub fn overlaps(&self, other: &Sections) -> bool {
+        self.range.contains(other.range.start()) || self.range.contains(other.range.end())
+    }
+}
+
+/// Every elf's assignment across the whole input rather than pair by pair.
+/// Elves are numbered in the order they're listed, from 0, so the elves of the
+/// first pair are 0 and 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Roster {
+    pub elves: Vec<Sections>,
+}
+
+impl Roster {
+    pub fn new(pairs: &[SectionsPair]) -> Self {
+        Self {
+            elves: pairs
+                .iter()
+                .flat_map(|pair| [pair.left.clone(), pair.right.clone()])
+                .collect(),
+        }
+    }
+
+    /// Every pair of elves with at least one section in common, each with the
+    /// lower numbered elf first, in order.
+    ///
+    /// Sweeps over the elves in order of their first section, keeping the ones
+    /// whose sections haven't ended yet, which are exactly the ones the next
+    /// elf overlaps.
+    pub fn overlapping(&self) -> Vec<(usize, usize)> {
+        let mut order = (0..self.elves.len())
+            .filter(|&elf| !self.elves[elf].range.is_empty())
+            .collect_vec();
+        order.sort_by_key(|&elf| *self.elves[elf].range.start());
+
+        let mut active: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+        let mut pairs = Vec::new();
+
+        for elf in order {
+            let range = &self.elves[elf].range;
+
+            while active
+                .peek()
+                .is_some_and(|Reverse((end, _))| end < range.start())
+            {
+                active.pop();
+            }
+
+            pairs.extend(
+                active
+                    .iter()
+                    .map(|&Reverse((_, other))| (other.min(elf), other.max(elf))),
+            );
+            active.push(Reverse((*range.end(), elf)));
+        }
+
+        pairs.sort_unstable();
+        pairs
+    }
+
+    /// How many elves cover each section, from section 1 to the last one any
+    /// elf covers, as runs of sections covered by the same number of elves.
+    pub fn coverage(&self) -> Vec<(RangeInclusive<usize>, usize)> {
+        let mut events = self
+            .elves
+            .iter()
+            .filter(|elf| !elf.range.is_empty())
+            .flat_map(|elf| [(*elf.range.start(), 1), (*elf.range.end() + 1, -1)])
+            .collect_vec();
+        events.sort_unstable();
+
+        let mut runs = Vec::new();
+        let (mut section, mut covering) = (1, 0_isize);
+
+        for (at, change) in events {
+            if at > section {
+                runs.push((section..=at - 1, covering as usize));
+                section = at;
+            }
+
+            covering += change;
+        }
+
+        // neighbouring runs can be covered by the same number of elves when
+        // one elf takes over right where another one stops
+        runs.into_iter()
+            .coalesce(|(a, count_a), (b, count_b)| {
+                if count_a == count_b {
+                    Ok((*a.start()..=*b.end(), count_a))
+                } else {
+                    Err(((a, count_a), (b, count_b)))
+                }
+            })
+            .collect()
+    }
+
+    /// The most elves covering any one section.
+    pub fn max_coverage(&self) -> usize {
+        self.coverage()
+            .into_iter()
+            .map(|(_, count)| count)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The sections nobody covers, from section 1 to the last one any elf does.
+    pub fn uncovered(&self) -> Vec<RangeInclusive<usize>> {
+        self.coverage()
+            .into_iter()
+            .filter(|(_, count)| *count == 0)
+            .map(|(range, _)| range)
+            .collect()
+    }
+}
+
+/// Draws every elf's sections the way the puzzle does, along with how many
+/// elves cover each section:
+///
+/// ```text
+/// .234.....  2-4
+/// .....678.  6-8
+/// .111.111.  elves per section
+/// ```
+///
+/// A section is drawn as the last digit of its number, and counts of more
+/// than 9 elves as `+`.
+impl Display for Roster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let coverage = self.coverage();
+        let last = coverage.last().map_or(0, |(range, _)| *range.end());
+        let width = last + 1;
+
+        for elf in &self.elves {
+            let line = (1..=width)
+                .map(|section| match elf.range.contains(&section) {
+                    true => char::from_digit((section % 10) as u32, 10).unwrap(),
+                    false => '.',
+                })
+                .collect::<String>();
+
+            writeln!(f, "{line}  {}-{}", elf.range.start(), elf.range.end())?;
+        }
+
+        for (range, count) in &coverage {
+            let c = match count {
+                0 => '.',
+                1..=9 => char::from_digit(*count as u32, 10).unwrap(),
+                _ => '+',
+            };
+
+            for _ in range.clone() {
+                f.write_char(c)?;
+            }
+        }
+
+        write!(f, ".  elves per section")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    const EXAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
+    fn roster(input: &str) -> Roster {
+        Roster::new(&parse(input).unwrap())
+    }
+
+    #[test]
+    fn test_task_1() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()).unwrap(), Answer::Integer(2));
+    }
+
+    #[test]
+    fn test_task_2() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()).unwrap(), Answer::Integer(4));
+    }
+
+    #[test_case(EXAMPLE ; "example")]
+    #[test_case("1-1,1-1\n1-1,2-2" ; "single sections")]
+    #[test_case("3-5,1-2\n6-9,2-3\n5-6,9-12" ; "touching")]
+    #[test_case("4-2,1-9" ; "empty")]
+    fn test_overlapping(input: &str) {
+        let roster = roster(input);
+
+        let expected = (0..roster.elves.len())
+            .tuple_combinations()
+            .filter(|&(a, b)| {
+                let (a, b) = (&roster.elves[a].range, &roster.elves[b].range);
+
+                a.clone().any(|section| b.contains(&section))
+            })
+            .collect_vec();
+
+        assert_eq!(roster.overlapping(), expected);
+    }
+
+    #[test]
+    fn test_overlapping_pairs_of_example() {
+        let overlapping = roster(EXAMPLE).overlapping();
+
+        // every pair part 2 counts, along with plenty more across lines
+        assert!([(4, 5), (6, 7), (8, 9), (10, 11)]
+            .iter()
+            .all(|pair| overlapping.contains(pair)));
+        assert!(!overlapping.contains(&(0, 1)));
+        assert_eq!(overlapping.len(), 49);
+    }
+
+    #[test]
+    fn test_coverage() {
+        let roster = roster(EXAMPLE);
+
+        assert_eq!(
+            roster.coverage(),
+            [
+                (1..=1, 0),
+                (2..=2, 4),
+                (3..=3, 5),
+                (4..=5, 7),
+                (6..=6, 8),
+                (7..=7, 6),
+                (8..=8, 4),
+                (9..=9, 1),
+            ]
+        );
+        assert_eq!(roster.max_coverage(), 8);
+        assert_eq!(roster.uncovered(), [1..=1]);
+    }
+
+    #[test]
+    fn test_uncovered() {
+        let roster = roster("1-2,5-6\n8-9,5-5");
+
+        assert_eq!(roster.uncovered(), [3..=4, 7..=7]);
+        assert_eq!(roster.max_coverage(), 2);
+    }
+
+    #[test]
+    fn test_coverage_of_no_one() {
+        let roster = roster("");
+
+        assert_eq!(roster.coverage(), []);
+        assert_eq!(roster.max_coverage(), 0);
+        assert_eq!(roster.to_string(), ".  elves per section");
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = "\
+.234.....  2-4
+.....678.  6-8
+.111.111.  elves per section";
+
+        assert_eq!(roster("2-4,6-8").to_string(), expected);
+    }
+
+    #[test]
+    fn test_display_example() {
+        let expected = "\
+.234......  2-4
+.....678..  6-8
+.23.......  2-3
+...45.....  4-5
+....567...  5-7
+......789.  7-9
+.2345678..  2-8
+..34567...  3-7
+.....6....  6-6
+...456....  4-6
+.23456....  2-6
+...45678..  4-8
+.45778641.  elves per section";
+
+        assert_eq!(roster(EXAMPLE).to_string(), expected);
+    }
+}
@@ -0,0 +1,825 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt::Display,
+    ops::{Index, IndexMut, Range},
+    str::FromStr,
+};
+
+use crate::{
+    prelude::*,
+    visual::{Frame, Simulation},
+};
+
+pub const INPUT: &str = include_str!("../../input/2022/day5.txt");
+
+pub fn part1((instructions, port): &(Vec<Instruction>, CargoPort)) -> Result<Answer> {
+    let port = port
+        .clone()
+        .with_crane(Crane::CrateMover)
+        .process(instructions)?;
+
+    Ok(port.top_crates().into())
+}
+
+pub fn part2((instructions, port): &(Vec<Instruction>, CargoPort)) -> Result<Answer> {
+    let port = port
+        .clone()
+        .with_crane(Crane::CrateMover9001)
+        .process(instructions)?;
+
+    Ok(port.top_crates().into())
+}
+
+pub fn parse(input: &str) -> Result<(Vec<Instruction>, CargoPort)> {
+    let (crates, instructions) = input
+        .split_once("\n\n")
+        .ok_or_else(|| anyhow!("malformed input"))?;
+
+    let port: CargoPort = crates
+        .lines()
+        .map(|s| s.to_string())
+        .collect::<Vec<String>>()
+        .try_into()?;
+
+    let instructions: Vec<Instruction> = instructions
+        .lines()
+        .map(|line| line.parse::<Instruction>())
+        .collect::<Result<Vec<Instruction>>>()?;
+
+    Ok((instructions, port))
+}
+
+/// A crate's label, which is usually a single letter but can be any number of
+/// characters.
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Clone)]
+pub struct Crate(String);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CargoPort {
+    crane: Crane,
+    sections: Vec<Vec<Crate>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Crane {
+    Missing,
+    CrateMover,
+    CrateMover9001,
+}
+
+impl Crane {
+    /// Carries out every instruction, or none of them: the instructions are
+    /// checked against the stacks before anything is moved, and the first one
+    /// that can't be carried out is named in the error.
+    pub fn process_instructions(
+        &self,
+        stacks: &mut [Vec<Crate>],
+        instructions: &[Instruction],
+    ) -> Result<()> {
+        self.validate(stacks, instructions)?;
+
+        instructions.iter().for_each(|i| self.apply(stacks, i));
+
+        Ok(())
+    }
+
+    /// Checks that the instructions can be carried out one after the other,
+    /// keeping track of only how tall each stack gets.
+    pub fn validate(&self, stacks: &[Vec<Crate>], instructions: &[Instruction]) -> Result<()> {
+        if *self == Crane::Missing {
+            bail!("No working crane");
+        }
+
+        let mut heights = stacks.iter().map(Vec::len).collect_vec();
+
+        for (idx, i) in instructions.iter().enumerate() {
+            let fail = |reason: String| anyhow!("instruction {} ({i}): {reason}", idx + 1);
+
+            for stack in [i.from, i.to] {
+                if stack >= heights.len() {
+                    return Err(fail(format!(
+                        "there is no stack {}, only {}",
+                        stack + 1,
+                        heights.len()
+                    )));
+                }
+            }
+
+            if heights[i.from] < i.amount {
+                return Err(fail(format!(
+                    "stack {} only has {} crates",
+                    i.from + 1,
+                    heights[i.from]
+                )));
+            }
+
+            heights[i.from] -= i.amount;
+            heights[i.to] += i.amount;
+        }
+
+        Ok(())
+    }
+
+    /// Carries out an instruction that is known to be valid. Moving crates
+    /// onto the stack they came from leaves it as it was, whichever the crane.
+    fn apply(&self, stacks: &mut [Vec<Crate>], i: &Instruction) {
+        if i.from == i.to {
+            return;
+        }
+
+        let from = &mut stacks[i.from];
+        let moved = from.split_off(from.len() - i.amount);
+
+        match self {
+            Crane::Missing => unreachable!("validated before moving anything"),
+            Crane::CrateMover => stacks[i.to].extend(moved.into_iter().rev()),
+            Crane::CrateMover9001 => stacks[i.to].extend(moved),
+        }
+    }
+}
+
+/// A port along with every instruction that has been carried out on it, which
+/// can be rolled back and replayed to any step.
+///
+/// Moving the crates back is just another move from where they were moved to:
+/// one at a time for the `CrateMover`, which reverses them again, or all at
+/// once for the `CrateMover9001`, which never did.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    port: CargoPort,
+    instructions: Vec<Instruction>,
+    step: usize,
+}
+
+impl Journal {
+    pub fn port(&self) -> &CargoPort {
+        &self.port
+    }
+
+    /// How many instructions have been carried out.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Moves the crates back or forth until exactly `step` instructions are
+    /// carried out.
+    pub fn seek(&mut self, step: usize) -> Result<()> {
+        if step > self.instructions.len() {
+            bail!(
+                "there are only {} steps, can't go to step {step}",
+                self.instructions.len()
+            );
+        }
+
+        let crane = &self.port.crane;
+        let stacks = &mut self.port.sections;
+
+        while self.step < step {
+            crane.apply(stacks, &self.instructions[self.step]);
+            self.step += 1;
+        }
+
+        while self.step > step {
+            self.step -= 1;
+            crane.apply(stacks, &self.instructions[self.step].reversed());
+        }
+
+        Ok(())
+    }
+
+    /// Undoes every instruction, leaving the port as it was before.
+    pub fn rollback(mut self) -> CargoPort {
+        self.seek(0).expect("step 0 always exists");
+
+        self.port
+    }
+}
+
+impl CargoPort {
+    pub fn with_capacity(cap: usize) -> Self {
+        Self {
+            crane: Crane::Missing,
+            sections: vec![vec![]; cap],
+        }
+    }
+
+    pub fn with_crane(self, crane: Crane) -> Self {
+        Self {
+            sections: self.sections,
+            crane,
+        }
+    }
+
+    pub fn process(mut self, instructions: &[Instruction]) -> Result<Self> {
+        self.crane
+            .process_instructions(&mut self.sections, instructions)?;
+
+        Ok(self)
+    }
+
+    /// Carries out every instruction like [`CargoPort::process`], but keeps
+    /// them so they can be rolled back or replayed.
+    pub fn journal(mut self, instructions: &[Instruction]) -> Result<Journal> {
+        self.crane
+            .process_instructions(&mut self.sections, instructions)?;
+
+        Ok(Journal {
+            port: self,
+            instructions: instructions.to_vec(),
+            step: instructions.len(),
+        })
+    }
+
+    /// Finds the fewest instructions that get this port's crane to rearrange
+    /// the crates into the `target` arrangement, failing if it takes more than
+    /// `bound` of them.
+    ///
+    /// Searches breadth first through every arrangement reachable from here, so
+    /// the first one to match is reached in as few instructions as possible.
+    pub fn plan(&self, target: &CargoPort, bound: usize) -> Result<Vec<Instruction>> {
+        if self.crane == Crane::Missing {
+            bail!("No working crane");
+        }
+
+        if self.sections.len() != target.sections.len() {
+            bail!(
+                "can't rearrange {} stacks into {}",
+                self.sections.len(),
+                target.sections.len()
+            );
+        }
+
+        let crates = |port: &CargoPort| {
+            port.sections
+                .iter()
+                .flatten()
+                .cloned()
+                .sorted()
+                .collect_vec()
+        };
+        if crates(self) != crates(target) {
+            bail!("the target arrangement doesn't have the same crates");
+        }
+
+        // every arrangement seen so far, along with the one it was reached from
+        // and the instruction that got there
+        let mut seen = HashMap::from([(self.sections.clone(), None)]);
+        let mut queue = VecDeque::from([(self.sections.clone(), 0)]);
+
+        while let Some((stacks, depth)) = queue.pop_front() {
+            if stacks == target.sections {
+                let mut plan = Vec::new();
+                let mut at = &stacks;
+
+                while let Some((previous, instruction)) = &seen[at] {
+                    plan.push(Instruction::clone(instruction));
+                    at = previous;
+                }
+
+                plan.reverse();
+                return Ok(plan);
+            }
+
+            if depth == bound {
+                continue;
+            }
+
+            for (from, to) in (0..stacks.len()).cartesian_product(0..stacks.len()) {
+                if from == to {
+                    continue;
+                }
+
+                for amount in 1..=stacks[from].len() {
+                    let instruction = Instruction { amount, from, to };
+                    let mut next = stacks.clone();
+                    self.crane.apply(&mut next, &instruction);
+
+                    if let Entry::Vacant(entry) = seen.entry(next.clone()) {
+                        entry.insert(Some((stacks.clone(), instruction)));
+                        queue.push_back((next, depth + 1));
+                    }
+                }
+            }
+        }
+
+        bail!("there is no plan with at most {bound} instructions")
+    }
+
+    /// The labels of the crates on top of every stack, skipping empty ones.
+    pub fn top_crates(&self) -> String {
+        self.sections
+            .iter()
+            .filter_map(|s| s.last())
+            .map(|c| c.0.as_str())
+            .collect::<String>()
+    }
+}
+
+impl Index<usize> for CargoPort {
+    type Output = Vec<Crate>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.sections.index(index)
+    }
+}
+
+impl IndexMut<usize> for CargoPort {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.sections.index_mut(index)
+    }
+}
+
+/// Reads the diagram column by column: the numbered footer says where each
+/// stack is, and every crate belongs to the stack whose number it sits over.
+/// Lines don't have to be padded, and neither labels nor stack numbers have to
+/// be a single character.
+impl TryFrom<Vec<String>> for CargoPort {
+    type Error = Error;
+
+    fn try_from(value: Vec<String>) -> Result<Self> {
+        let (footer, rows) = value
+            .split_last()
+            .ok_or_else(|| anyhow!("missing stack numbers"))?;
+
+        let columns = tokens(footer, |token| token.chars().all(|c| c.is_ascii_digit()))?;
+        for (idx, (_, number)) in columns.iter().enumerate() {
+            if number.parse::<usize>()? != idx + 1 {
+                bail!("expected stack {} but found stack {number}", idx + 1);
+            }
+        }
+
+        let mut port = CargoPort::with_capacity(columns.len());
+
+        for (level, row) in rows.iter().rev().enumerate() {
+            let crates = tokens(row, |token| {
+                token.len() > 2 && token.starts_with('[') && token.ends_with(']')
+            })?;
+
+            for (span, token) in crates {
+                let label = &token[1..token.len() - 1];
+                let idx = columns
+                    .iter()
+                    .position(|(column, _)| column.start < span.end && span.start < column.end)
+                    .ok_or_else(|| anyhow!("crate {token} isn't over any stack"))?;
+
+                match port[idx].len() {
+                    len if len < level => {
+                        bail!(
+                            "crate {token} floats over an empty spot in stack {}",
+                            idx + 1
+                        )
+                    }
+                    len if len > level => {
+                        bail!(
+                            "crate {token} sits on top of another one in stack {}",
+                            idx + 1
+                        )
+                    }
+                    _ => port[idx].push(Crate(label.to_string())),
+                }
+            }
+        }
+
+        Ok(port)
+    }
+}
+
+/// Splits a line of the diagram on spaces into the columns each part spans,
+/// failing on any part that isn't `valid`.
+fn tokens(line: &str, valid: impl Fn(&str) -> bool) -> Result<Vec<(Range<usize>, &str)>> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+
+    for (column, (idx, c)) in line.char_indices().chain([(line.len(), ' ')]).enumerate() {
+        match (start, c == ' ') {
+            (None, false) => start = Some((column, idx)),
+            (Some((first, from)), true) => {
+                let token = &line[from..idx];
+                if !valid(token) {
+                    bail!("unexpected {token} in line {line:?}");
+                }
+
+                tokens.push((first..column, token));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Draws the stacks in the same notation as the puzzle input, crates in
+/// brackets above a numbered footer, so whatever is printed can be parsed back
+/// into the same port. Every line is padded to the full width, just like the
+/// input is.
+impl Display for CargoPort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.diagram().join("\n"))
+    }
+}
+
+impl CargoPort {
+    /// Every column is as wide as the widest crate or stack number, with the
+    /// crates and numbers centered in them.
+    fn diagram(&self) -> Vec<String> {
+        let height = self.sections.iter().map(Vec::len).max().unwrap_or(0);
+        let width = self
+            .sections
+            .iter()
+            .flatten()
+            .map(|Crate(label)| label.chars().count() + 2)
+            .chain([3, self.sections.len().to_string().len()])
+            .max()
+            .unwrap();
+
+        let mut lines = (0..height)
+            .rev()
+            .map(|level| {
+                self.sections
+                    .iter()
+                    .map(|section| match section.get(level) {
+                        Some(Crate(label)) => format!("{:^width$}", format!("[{label}]")),
+                        None => " ".repeat(width),
+                    })
+                    .join(" ")
+            })
+            .collect_vec();
+        lines.push(
+            (1..=self.sections.len())
+                .map(|idx| format!("{idx:^width$}"))
+                .join(" "),
+        );
+
+        lines
+    }
+}
+
+pub fn simulation(
+    (instructions, port): &(Vec<Instruction>, CargoPort),
+    part: u8,
+) -> Result<Box<dyn Simulation>> {
+    let crane = match part {
+        1 => Crane::CrateMover,
+        2 => Crane::CrateMover9001,
+        _ => bail!("There is no part {part}"),
+    };
+
+    crane.validate(&port.sections, instructions)?;
+
+    Ok(Box::new(CraneSimulation {
+        port: port.clone().with_crane(crane),
+        instructions: instructions.clone(),
+        done: 0,
+    }))
+}
+
+/// Carries out a single move per step, drawing the stacks the way the puzzle
+/// does.
+pub struct CraneSimulation {
+    port: CargoPort,
+    instructions: Vec<Instruction>,
+    done: usize,
+}
+
+impl Simulation for CraneSimulation {
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.instructions.get(self.done) else {
+            return false;
+        };
+
+        // every instruction was validated when the simulation was set up
+        self.port.crane.apply(&mut self.port.sections, instruction);
+        self.done += 1;
+
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        let sections = &self.port.sections;
+        let grid = self.port.diagram();
+
+        let tops = sections
+            .iter()
+            .map(|section| section.last().map_or(" ", |c| c.0.as_str()))
+            .collect::<String>();
+
+        Frame {
+            title: format!(
+                "{} of {} moves done, top crates: {tops}",
+                self.done,
+                self.instructions.len()
+            ),
+            grid,
+            focus: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Instruction {
+    pub amount: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl Instruction {
+    /// The instruction that puts the crates back where they came from.
+    fn reversed(&self) -> Self {
+        Self {
+            amount: self.amount,
+            from: self.to,
+            to: self.from,
+        }
+    }
+}
+
+/// Written the same way as in the input, with stacks numbered from 1.
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.amount,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parts = s.split(' ').filter_map(|p| p.parse::<usize>().ok());
+
+        let err = || anyhow!("invalid length");
+        let stack = |n: usize| {
+            n.checked_sub(1)
+                .ok_or_else(|| anyhow!("stacks are numbered from 1"))
+        };
+
+        Ok(Self {
+            amount: parts.next().ok_or_else(err)?,
+            from: stack(parts.next().ok_or_else(err)?)?,
+            to: stack(parts.next().ok_or_else(err)?)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use proptest::prelude::*;
+    use test_case::test_case;
+
+    fn reparse(port: &CargoPort) -> Result<CargoPort> {
+        port.to_string()
+            .lines()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .try_into()
+    }
+
+    #[test]
+    fn test_display() {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (crates, _) = input.split_once("\n\n").unwrap();
+        let (_, port) = parse(input).unwrap();
+
+        assert_eq!(port.to_string(), crates);
+    }
+
+    #[test_case("move 1 from 4 to 1", "instruction 2 (move 1 from 4 to 1): there is no stack 4, only 3" ; "missing stack")]
+    #[test_case("move 3 from 2 to 1", "instruction 2 (move 3 from 2 to 1): stack 2 only has 2 crates" ; "too few crates")]
+    #[test_case("move 3 from 1 to 3\nmove 1 from 1 to 2", "instruction 3 (move 1 from 1 to 2): stack 1 only has 0 crates" ; "emptied earlier")]
+    fn test_invalid_instructions(extra: &str, expected: &str) {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (mut instructions, port) = parse(input).unwrap();
+        instructions.truncate(1);
+        instructions.extend(extra.lines().map(|line| line.parse().unwrap()));
+
+        let mut stacks = port.sections.clone();
+        let err = Crane::CrateMover
+            .process_instructions(&mut stacks, &instructions)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), expected);
+        assert_eq!(stacks, port.sections);
+    }
+
+    #[test_case(Crane::CrateMover)]
+    #[test_case(Crane::CrateMover9001)]
+    fn test_move_onto_the_same_stack(crane: Crane) {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (_, port) = parse(input).unwrap();
+        let port = port.with_crane(crane);
+        let instructions = ["move 2 from 1 to 1".parse().unwrap()];
+
+        let mut journal = port.clone().journal(&instructions).unwrap();
+        assert_eq!(journal.port(), &port);
+
+        journal.seek(0).unwrap();
+        assert_eq!(journal.port(), &port);
+    }
+
+    #[test]
+    fn test_instruction_from_stack_zero() {
+        assert!("move 1 from 0 to 1".parse::<Instruction>().is_err());
+    }
+
+    #[test_case(Crane::CrateMover, "CMZ")]
+    #[test_case(Crane::CrateMover9001, "MCD")]
+    fn test_journal(crane: Crane, tops: &str) {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (instructions, port) = parse(input).unwrap();
+        let port = port.with_crane(crane);
+
+        let mut journal = port.clone().journal(&instructions).unwrap();
+        assert_eq!(journal.step(), 4);
+        assert_eq!(journal.port().top_crates(), tops);
+
+        for step in [2, 0, 3, 1, 4] {
+            journal.seek(step).unwrap();
+
+            let expected = port.clone().process(&instructions[..step]).unwrap();
+            assert_eq!(journal.port(), &expected);
+        }
+
+        assert!(journal.seek(5).is_err());
+        assert_eq!(journal.rollback(), port);
+    }
+
+    fn port(lines: &[&str]) -> Result<CargoPort> {
+        lines
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<String>>()
+            .try_into()
+    }
+
+    fn stacks(port: &CargoPort) -> Vec<Vec<&str>> {
+        port.sections
+            .iter()
+            .map(|section| section.iter().map(|c| c.0.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_wide() {
+        let port = port(&[
+            "                                        [K]",
+            "[A] [B]                                 [L]",
+            " 1   2   3   4   5   6   7   8   9  10  11",
+        ])
+        .unwrap();
+
+        assert_eq!(port.sections.len(), 11);
+        assert_eq!(stacks(&port)[0], ["A"]);
+        assert_eq!(stacks(&port)[2], Vec::<&str>::new());
+        assert_eq!(stacks(&port)[10], ["L", "K"]);
+    }
+
+    #[test]
+    fn test_top_crates_of_empty_stacks() {
+        let port = port(&["[A]         [D]", " 1   2   3   4 "]).unwrap();
+        let instructions = ["move 1 from 1 to 3".parse().unwrap()];
+
+        assert_eq!(port.top_crates(), "AD");
+        assert_eq!(
+            port.with_crane(Crane::CrateMover)
+                .process(&instructions)
+                .unwrap()
+                .top_crates(),
+            "AD"
+        );
+    }
+
+    #[test]
+    fn test_parse_ragged_multi_character_labels() {
+        let port = port(&["       [CC]", "[AAA]  [B]", "  1     2"]).unwrap();
+
+        assert_eq!(stacks(&port), [vec!["AAA"], vec!["B", "CC"]]);
+        assert_eq!(port.to_string(), "      [CC] \n[AAA]  [B] \n  1     2  ");
+    }
+
+    #[test_case(&["[A]    ", "    [B]", " 1   2 "], "crate [A] floats over an empty spot in stack 1" ; "floating")]
+    #[test_case(&["        [A]", " 1   2 "], "crate [A] isn't over any stack" ; "outside")]
+    #[test_case(&["[A]", " 1   3 "], "expected stack 2 but found stack 3" ; "numbering")]
+    #[test_case(&["[A] B", " 1   2 "], "unexpected B in line \"[A] B\"" ; "unbracketed")]
+    #[test_case(&[], "missing stack numbers" ; "empty")]
+    fn test_parse_invalid(lines: &[&str], expected: &str) {
+        assert_eq!(port(lines).unwrap_err().to_string(), expected);
+    }
+
+    #[test_case(Crane::CrateMover, 1)]
+    #[test_case(Crane::CrateMover9001, 3)]
+    fn test_plan_reversing_a_stack(crane: Crane, moves: usize) {
+        let start = port(&["[C]    ", "[B]    ", "[A]    ", " 1   2 "]).unwrap();
+        let target = port(&["    [A]", "    [B]", "    [C]", " 1   2 "]).unwrap();
+
+        let start = start.with_crane(crane);
+        let plan = start.plan(&target, 5).unwrap();
+
+        assert_eq!(plan.len(), moves);
+        assert_eq!(start.process(&plan).unwrap().sections, target.sections);
+    }
+
+    #[test_case(Crane::CrateMover)]
+    #[test_case(Crane::CrateMover9001)]
+    fn test_plan_example(crane: Crane) {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (instructions, port) = parse(input).unwrap();
+        let port = port.with_crane(crane);
+        let target = port.clone().process(&instructions).unwrap();
+
+        let plan = port.plan(&target, instructions.len()).unwrap();
+
+        assert!(plan.len() <= instructions.len());
+        assert_eq!(port.process(&plan).unwrap(), target);
+    }
+
+    #[test]
+    fn test_plan_out_of_reach() {
+        let start = port(&["[C]    ", "[B]    ", "[A]    ", " 1   2 "]).unwrap();
+        let target = port(&["    [A]", "    [B]", "    [C]", " 1   2 "]).unwrap();
+
+        let err = start
+            .with_crane(Crane::CrateMover9001)
+            .plan(&target, 2)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "there is no plan with at most 2 instructions"
+        );
+    }
+
+    #[test]
+    fn test_plan_different_crates() {
+        let start = port(&["[A]    ", " 1   2 "]).unwrap();
+        let target = port(&["    [B]", " 1   2 "]).unwrap();
+
+        assert!(start
+            .with_crane(Crane::CrateMover)
+            .plan(&target, 5)
+            .is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_round_trip(sections in prop::collection::vec(
+            prop::collection::vec("[A-Z0-9]{1,3}".prop_map(Crate), 0..8),
+            1..15,
+        )) {
+            let port = CargoPort { crane: Crane::Missing, sections };
+
+            prop_assert_eq!(reparse(&port).unwrap(), port);
+        }
+    }
+
+    #[test_case(1, "CMZ", &["        [Z]", "        [N]", "        [D]", "[C] [M] [P]", " 1   2   3 "])]
+    #[test_case(2, "MCD", &["        [D]", "        [N]", "        [Z]", "[M] [C] [P]", " 1   2   3 "])]
+    fn test_simulation(part: u8, tops: &str, expected: &[&str]) {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let mut simulation = simulation(&parse(input).unwrap(), part).unwrap();
+
+        assert_eq!(
+            simulation.frame().grid,
+            ["    [D]    ", "[N] [C]    ", "[Z] [M] [P]", " 1   2   3 "]
+        );
+
+        while simulation.step() {}
+
+        let frame = simulation.frame();
+        assert_eq!(
+            frame.title,
+            format!("4 of 4 moves done, top crates: {tops}")
+        );
+        assert_eq!(frame.grid, expected);
+    }
+
+    #[test]
+    fn test_simulation_of_invalid_instructions() {
+        let input = include_str!("../../input/2022/day5_example.txt");
+        let (mut instructions, port) = parse(input).unwrap();
+        instructions.push("move 9 from 1 to 2".parse().unwrap());
+
+        let err = simulation(&(instructions, port), 1).err().unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "instruction 5 (move 9 from 1 to 2): stack 1 only has 1 crates"
+        );
+    }
+}
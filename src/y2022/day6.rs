@@ -0,0 +1,409 @@
+use std::io::{ErrorKind, Read};
+
+use crate::{params, prelude::*};
+
+pub const INPUT: &str = include_str!("../../input/2022/day6.txt");
+
+/// How many different characters in a row mark the start of a packet, and of a
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub packet: usize,
+    pub message: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            packet: 4,
+            message: 14,
+        }
+    }
+}
+
+/// The most different bytes there can be in a row, as there are only this many
+/// different bytes.
+pub const MAX_SPAN: usize = 256;
+
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let size = match name {
+            "packet" => &mut self.packet,
+            "message" => &mut self.message,
+            _ => bail!("There is no parameter called {name}"),
+        };
+
+        let value = value.parse()?;
+        if value > MAX_SPAN {
+            bail!("There are only {MAX_SPAN} different bytes, so {value} in a row are never all different");
+        }
+
+        *size = value;
+
+        Ok(())
+    }
+}
+
+pub fn parse(input: &str) -> Result<String> {
+    Ok(input.trim().to_string())
+}
+
+pub fn part1(datastream: &str, params: &Params) -> Result<Answer> {
+    datastream
+        .find_unique_span_idx(params.packet)
+        .map(Answer::from)
+        .ok_or_else(|| anyhow!("Did not find unique span size"))
+}
+
+pub fn part2(datastream: &str, params: &Params) -> Result<Answer> {
+    datastream
+        .find_unique_span_idx(params.message)
+        .map(Answer::from)
+        .ok_or_else(|| anyhow!("Did not find unique span size"))
+}
+
+pub trait UniqueSpan {
+    /// Where every run of `size` different bytes ends, as the number of bytes
+    /// up to and including the run.
+    fn unique_spans(&self, size: usize) -> Markers<'_>;
+
+    /// Where the first run of `size` different bytes ends.
+    fn find_unique_span_idx(&self, size: usize) -> Option<usize> {
+        self.unique_spans(size).next()
+    }
+}
+
+impl UniqueSpan for [u8] {
+    fn unique_spans(&self, size: usize) -> Markers<'_> {
+        Markers {
+            bytes: self,
+            size,
+            counts: Counts::default(),
+            end: 0,
+        }
+    }
+}
+
+impl UniqueSpan for str {
+    fn unique_spans(&self, size: usize) -> Markers<'_> {
+        self.as_bytes().unique_spans(size)
+    }
+}
+
+/// How many times each byte is in a window, and how many different bytes are
+/// in it more than once, so telling whether they're all different doesn't
+/// depend on how wide the window is.
+struct Counts {
+    counts: [usize; 256],
+    repeated: usize,
+}
+
+impl Default for Counts {
+    fn default() -> Self {
+        Self {
+            counts: [0; 256],
+            repeated: 0,
+        }
+    }
+}
+
+impl Counts {
+    fn add(&mut self, byte: u8) {
+        let count = &mut self.counts[byte as usize];
+        *count += 1;
+        if *count == 2 {
+            self.repeated += 1;
+        }
+    }
+
+    fn remove(&mut self, byte: u8) {
+        let count = &mut self.counts[byte as usize];
+        if *count == 2 {
+            self.repeated -= 1;
+        }
+        *count -= 1;
+    }
+
+    fn unique(&self) -> bool {
+        self.repeated == 0
+    }
+}
+
+/// Slides a window of `size` bytes over the stream. Each byte is looked at
+/// twice, once on the way in and once on the way out, however wide the window
+/// is.
+pub struct Markers<'a> {
+    bytes: &'a [u8],
+    size: usize,
+    counts: Counts,
+    end: usize,
+}
+
+impl Iterator for Markers<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.size == 0 {
+            return None;
+        }
+
+        while self.end < self.bytes.len() {
+            self.counts.add(self.bytes[self.end]);
+            self.end += 1;
+
+            if self.end > self.size {
+                self.counts.remove(self.bytes[self.end - self.size - 1]);
+            }
+
+            if self.end >= self.size && self.counts.unique() {
+                return Some(self.end);
+            }
+        }
+
+        None
+    }
+}
+
+/// Where a marker ends, as the number of bytes read up to and including it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Marker {
+    Packet(usize),
+    Message(usize),
+}
+
+/// Reads the datastream a chunk at a time and reports the first start-of-packet
+/// and start-of-message markers as soon as each is found, so a stream of any
+/// size is searched in constant memory. It stops reading once it has found
+/// both.
+///
+/// Unlike [`parse`], nothing is trimmed: every byte read is part of the stream.
+pub fn markers<R: Read>(reader: R, params: &Params) -> StreamMarkers<R> {
+    StreamMarkers {
+        reader,
+        chunk: vec![0; CHUNK].into_boxed_slice(),
+        pos: 0,
+        len: 0,
+        offset: 0,
+        packet: Some(Window::new(params.packet)),
+        message: Some(Window::new(params.message)),
+        pending: None,
+    }
+}
+
+const CHUNK: usize = 64 * 1024;
+
+pub struct StreamMarkers<R> {
+    reader: R,
+    chunk: Box<[u8]>,
+    /// The part of the chunk that hasn't been looked at yet.
+    pos: usize,
+    len: usize,
+    offset: usize,
+    /// The windows of the markers that haven't been found yet.
+    packet: Option<Window>,
+    message: Option<Window>,
+    /// A message marker found on the same byte as the packet marker.
+    pending: Option<Marker>,
+}
+
+impl<R: Read> Iterator for StreamMarkers<R> {
+    type Item = Result<Marker>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(marker) = self.pending.take() {
+            return Some(Ok(marker));
+        }
+
+        while self.packet.is_some() || self.message.is_some() {
+            if self.pos == self.len {
+                match self.reader.read(&mut self.chunk) {
+                    Ok(0) => break,
+                    Ok(len) => (self.pos, self.len) = (0, len),
+                    Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                    Err(err) => {
+                        (self.packet, self.message) = (None, None);
+                        return Some(Err(err.into()));
+                    }
+                }
+            }
+
+            let byte = self.chunk[self.pos];
+            self.pos += 1;
+            self.offset += 1;
+
+            let packet = found(&mut self.packet, byte).then_some(Marker::Packet(self.offset));
+            let message = found(&mut self.message, byte).then_some(Marker::Message(self.offset));
+
+            match (packet, message) {
+                (Some(packet), message) => {
+                    self.pending = message;
+                    return Some(Ok(packet));
+                }
+                (None, Some(message)) => return Some(Ok(message)),
+                (None, None) => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Pushes the byte into the window, if it's still being looked for, and stops
+/// looking once it's found.
+fn found(window: &mut Option<Window>, byte: u8) -> bool {
+    let found = window.as_mut().is_some_and(|window| window.push(byte));
+    if found {
+        *window = None;
+    }
+
+    found
+}
+
+/// The last `size` bytes read, which carries over from one chunk to the next.
+/// A window wider than [`MAX_SPAN`] is never all different bytes, so it's kept
+/// empty instead, just like a window of no bytes.
+struct Window {
+    ring: Vec<u8>,
+    counts: Counts,
+    seen: usize,
+}
+
+impl Window {
+    fn new(size: usize) -> Self {
+        Self {
+            ring: vec![0; if size <= MAX_SPAN { size } else { 0 }],
+            counts: Counts::default(),
+            seen: 0,
+        }
+    }
+
+    /// Whether the window is all different bytes once `byte` is in it.
+    fn push(&mut self, byte: u8) -> bool {
+        let size = self.ring.len();
+        if size == 0 {
+            return false;
+        }
+
+        let slot = self.seen % size;
+        if self.seen >= size {
+            self.counts.remove(self.ring[slot]);
+        }
+
+        self.ring[slot] = byte;
+        self.counts.add(byte);
+        self.seen += 1;
+
+        self.seen >= size && self.counts.unique()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19)]
+    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23)]
+    #[test_case("nppdvjthqldpwncqszvftbrmjlhg", 6, 23)]
+    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26)]
+    fn test_examples(datastream: &str, packet: usize, message: usize) {
+        let params = Params::default();
+
+        assert_eq!(part1(datastream, &params).unwrap(), packet.into());
+        assert_eq!(part2(datastream, &params).unwrap(), message.into());
+    }
+
+    #[test_case("aabcd", 4, Some(5) ; "at the very end")]
+    #[test_case("abcd", 4, Some(4) ; "whole stream")]
+    #[test_case("abc", 4, None ; "too short")]
+    #[test_case("abcabc", 0, None ; "empty window")]
+    fn test_find_unique_span_idx(datastream: &str, size: usize, expected: Option<usize>) {
+        assert_eq!(datastream.find_unique_span_idx(size), expected);
+    }
+
+    /// Hands out the stream a few bytes at a time, so the windows have to carry
+    /// over from one read to the next.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.1.min(buf.len()).min(self.0.len());
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+
+            Ok(len)
+        }
+    }
+
+    #[test_case(1)]
+    #[test_case(3)]
+    #[test_case(CHUNK)]
+    fn test_markers(trickle: usize) {
+        let datastream = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let found = markers(Trickle(datastream.as_bytes(), trickle), &Params::default())
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(found, [Marker::Packet(7), Marker::Message(19)]);
+    }
+
+    #[test]
+    fn test_markers_on_the_same_byte() {
+        let params = Params {
+            packet: 3,
+            message: 3,
+        };
+        let found = markers("aabc".as_bytes(), &params)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(found, [Marker::Packet(4), Marker::Message(4)]);
+    }
+
+    #[test]
+    fn test_markers_match_slices() {
+        let datastream = INPUT.trim();
+        let params = Params::default();
+
+        let found = markers(datastream.as_bytes(), &params)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            found,
+            [
+                Marker::Packet(datastream.find_unique_span_idx(params.packet).unwrap()),
+                Marker::Message(datastream.find_unique_span_idx(params.message).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_params_too_wide() {
+        let mut params = Params::default();
+
+        assert!(params::Params::set(&mut params, "message", "256").is_ok());
+        assert!(params::Params::set(&mut params, "message", "10000000000").is_err());
+        assert!(params::Params::set(&mut params, "packet", "257").is_err());
+    }
+
+    #[test]
+    fn test_markers_too_wide() {
+        let params = Params {
+            packet: 4,
+            message: usize::MAX,
+        };
+        let found = markers("abcd".as_bytes(), &params)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(found, [Marker::Packet(4)]);
+    }
+
+    #[test]
+    fn test_unique_spans() {
+        assert_eq!("aabcbdd".unique_spans(3).collect_vec(), [4, 6]);
+        assert_eq!("abcd".unique_spans(1).collect_vec(), [1, 2, 3, 4]);
+    }
+}
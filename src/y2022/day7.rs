@@ -1,47 +1,72 @@
 use std::{collections::HashMap, path::PathBuf, str::FromStr};
 
-use crate::prelude::*;
+use crate::{params, prelude::*};
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day7.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day7.txt");
 
-    println!("{}", task1(input)?);
+/// The size of the disk, and how much of it has to be free for the update.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    pub disk: usize,
+    pub needed: usize,
+}
 
-    println!("{}", task2(input)?);
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            disk: 70_000_000,
+            needed: 30_000_000,
+        }
+    }
+}
 
-    Ok(())
+impl params::Params for Params {
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "disk" => self.disk = value.parse()?,
+            "needed" => self.needed = value.parse()?,
+            _ => bail!("There is no parameter called {name}"),
+        }
+
+        Ok(())
+    }
 }
 
-fn task1(input: &str) -> Result<usize> {
+pub fn parse(input: &str) -> Result<FileSystem> {
     let mut fs = FileSystem::new();
 
     for command in parse_commands(input)? {
         fs.apply(command)?;
     }
 
-    let sizes = fs.get_directory_sizes()?;
-
-    Ok(sizes.into_iter().filter(|n| *n <= 100_000).sum())
+    Ok(fs)
 }
 
-fn task2(input: &str) -> Result<usize> {
-    let mut fs = FileSystem::new();
+pub fn part1(fs: &FileSystem, _params: &Params) -> Result<Answer> {
+    let sizes = fs.get_directory_sizes()?;
 
-    for command in parse_commands(input)? {
-        fs.apply(command)?;
-    }
+    Ok(sizes
+        .into_iter()
+        .filter(|n| *n <= 100_000)
+        .sum::<usize>()
+        .into())
+}
 
+pub fn part2(fs: &FileSystem, params: &Params) -> Result<Answer> {
     let directory_sizes = fs.get_directory_sizes()?;
 
     let total_size: usize = *directory_sizes
         .iter()
         .max()
         .ok_or_else(|| anyhow!("numbers should have maxmum values"))?;
-    let free_space = 70000000 - total_size;
+    let free_space = params
+        .disk
+        .checked_sub(total_size)
+        .ok_or_else(|| anyhow!("{total_size} doesn't fit on a disk of {}", params.disk))?;
 
     let mut smallest_big_folder_sizes: Vec<usize> = directory_sizes
         .into_iter()
-        .filter(|n| *n + free_space > 30_000_000)
+        .filter(|n| *n + free_space > params.needed)
         .collect();
 
     smallest_big_folder_sizes.sort();
@@ -49,10 +74,11 @@ fn task2(input: &str) -> Result<usize> {
     smallest_big_folder_sizes
         .into_iter()
         .min()
+        .map(Answer::from)
         .ok_or_else(|| anyhow!("numbers should have minimum values"))
 }
 
-fn parse_commands(input: &str) -> Result<Vec<Command>> {
+pub fn parse_commands(input: &str) -> Result<Vec<Command>> {
     input
         .split("$ ")
         .filter(|part| !part.is_empty())
@@ -61,13 +87,13 @@ fn parse_commands(input: &str) -> Result<Vec<Command>> {
 }
 
 #[derive(Debug)]
-struct FileSystem {
+pub struct FileSystem {
     cwd: PathBuf,
     fs: HashMap<PathBuf, Entry>,
 }
 
 impl FileSystem {
-    fn new() -> Self {
+    pub fn new() -> Self {
         let root_path: PathBuf = "/".into();
         let mut fs = HashMap::new();
         fs.insert(root_path.clone(), Entry::dir("/"));
@@ -103,7 +129,7 @@ impl FileSystem {
         Ok(())
     }
 
-    fn apply(&mut self, command: Command) -> Result<()> {
+    pub fn apply(&mut self, command: Command) -> Result<()> {
         match command {
             Command::Ls(entries) => {
                 self.register_paths(entries)?;
@@ -126,7 +152,7 @@ impl FileSystem {
         }
     }
 
-    fn get_directory_sizes(&self) -> Result<Vec<usize>> {
+    pub fn get_directory_sizes(&self) -> Result<Vec<usize>> {
         fn walk_folders(fs: &FileSystem, pb: &PathBuf, res: &mut Vec<usize>) -> Result<usize> {
             let entry = fs
                 .fs
@@ -166,8 +192,14 @@ impl FileSystem {
     }
 }
 
+impl Default for FileSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Command {
+pub enum Command {
     Ls(Vec<Entry>),
     ChDir(Target),
 }
@@ -197,7 +229,7 @@ impl FromStr for Command {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Target {
+pub enum Target {
     Root,
     Up,
     Dir(String),
@@ -218,7 +250,7 @@ impl FromStr for Target {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum Entry {
+pub enum Entry {
     File(File),
     Dir(Directory),
 }
@@ -241,15 +273,15 @@ impl FromStr for Entry {
 }
 
 impl Entry {
-    fn file(name: &str, size: usize) -> Self {
+    pub fn file(name: &str, size: usize) -> Self {
         Self::File(File::new(name, size))
     }
 
-    fn dir(name: &str) -> Self {
+    pub fn dir(name: &str) -> Self {
         Self::Dir(Directory::new(name))
     }
 
-    fn name(&self) -> String {
+    pub fn name(&self) -> String {
         match self {
             Entry::File(f) => f.name.clone(),
             Entry::Dir(d) => d.name.clone(),
@@ -258,13 +290,13 @@ impl Entry {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Directory {
-    name: String,
-    children: Vec<String>,
+pub struct Directory {
+    pub name: String,
+    pub children: Vec<String>,
 }
 
 impl Directory {
-    fn new(name: &str) -> Self {
+    pub fn new(name: &str) -> Self {
         Self {
             name: name.to_string(),
             children: vec![],
@@ -273,13 +305,13 @@ impl Directory {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct File {
-    name: String,
-    size: usize,
+pub struct File {
+    pub name: String,
+    pub size: usize,
 }
 
 impl File {
-    fn new(name: &str, size: usize) -> Self {
+    pub fn new(name: &str, size: usize) -> Self {
         Self {
             name: name.to_string(),
             size,
@@ -311,15 +343,21 @@ mod tests {
 
     #[test]
     fn test_task_1() {
-        let input = include_str!("input/day7_example.txt");
+        let input = include_str!("../../input/2022/day7_example.txt");
 
-        assert_eq!(task1(input).unwrap(), 95437);
+        assert_eq!(
+            part1(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(95437)
+        );
     }
 
     #[test]
     fn test_task_2() {
-        let input = include_str!("input/day7_example.txt");
+        let input = include_str!("../../input/2022/day7_example.txt");
 
-        assert_eq!(task2(input).unwrap(), 24933642);
+        assert_eq!(
+            part2(&parse(input).unwrap(), &Params::default()).unwrap(),
+            Answer::Integer(24933642)
+        );
     }
 }
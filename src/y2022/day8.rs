@@ -1,17 +1,8 @@
 use crate::prelude::*;
 
-pub fn run() -> Result<()> {
-    let input = include_str!("input/day8.txt");
+pub const INPUT: &str = include_str!("../../input/2022/day8.txt");
 
-    println!("{}", task1(input)?);
-
-    println!("{}", task2(input)?);
-
-    Ok(())
-}
-
-fn task1(input: &str) -> Result<usize> {
-    let matrix = to_matrix(input);
+pub fn part1(matrix: &[Vec<usize>]) -> Result<Answer> {
     let len = matrix.len();
 
     let edges = len * 4 - 4;
@@ -19,11 +10,11 @@ fn task1(input: &str) -> Result<usize> {
     let visible_trees = (1..len - 1)
         .cartesian_product(1..len - 1)
         .filter(|(row, col)| {
-            visible_horizontal(&matrix, *row, *col) || visible_vertical(&matrix, *row, *col)
+            visible_horizontal(matrix, *row, *col) || visible_vertical(matrix, *row, *col)
         })
         .count();
 
-    Ok(visible_trees + edges)
+    Ok((visible_trees + edges).into())
 }
 
 fn visible_vertical(matrix: &[Vec<usize>], row: usize, col: usize) -> bool {
@@ -48,14 +39,14 @@ fn visible_horizontal(matrix: &[Vec<usize>], row: usize, col: usize) -> bool {
     visible_left || visible_right
 }
 
-fn task2(input: &str) -> Result<usize> {
-    let matrix = to_matrix(input);
+pub fn part2(matrix: &[Vec<usize>]) -> Result<Answer> {
     let len = matrix.len();
 
     (1..len - 1)
         .cartesian_product(1..len - 1)
-        .map(|(row, col)| score_horizontal(&matrix, row, col) * score_vertical(&matrix, row, col))
+        .map(|(row, col)| score_horizontal(matrix, row, col) * score_vertical(matrix, row, col))
         .max()
+        .map(Answer::from)
         .ok_or_else(|| anyhow!("No max for usizes: press X for doubt."))
 }
 
@@ -105,15 +96,19 @@ fn score_horizontal(matrix: &[Vec<usize>], row: usize, col: usize) -> usize {
     score_left * score_right
 }
 
-fn to_matrix(input: &str) -> Vec<Vec<usize>> {
+pub fn parse(input: &str) -> Result<Vec<Vec<usize>>> {
     input
         .lines()
         .map(|line| {
             line.chars()
-                .map(|c| c.to_digit(10).unwrap() as usize)
-                .collect::<Vec<usize>>()
+                .map(|c| {
+                    c.to_digit(10)
+                        .map(|d| d as usize)
+                        .ok_or_else(|| anyhow!("Not a tree height: {c}"))
+                })
+                .collect::<Result<Vec<usize>>>()
         })
-        .collect::<Vec<Vec<usize>>>()
+        .collect::<Result<Vec<Vec<usize>>>>()
 }
 
 #[cfg(test)]
@@ -123,21 +118,21 @@ mod tests {
 
     #[test]
     fn test_task_1() {
-        let input = include_str!("input/day8_example.txt");
+        let input = include_str!("../../input/2022/day8_example.txt");
 
-        assert_eq!(task1(input).unwrap(), 21)
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), Answer::Integer(21))
     }
 
     #[test]
     fn test_task_2() {
-        let input = include_str!("input/day8_example.txt");
+        let input = include_str!("../../input/2022/day8_example.txt");
 
-        assert_eq!(task2(input).unwrap(), 8)
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), Answer::Integer(8))
     }
 
     #[test]
     fn test_score_for_tree() {
-        let input = to_matrix(include_str!("input/day8_example.txt"));
+        let input = parse(include_str!("../../input/2022/day8_example.txt")).unwrap();
 
         assert_eq!(score_vertical(&input, 3, 2), 2);
         assert_eq!(score_horizontal(&input, 3, 2), 4)
@@ -0,0 +1,350 @@
+use std::{collections::HashSet, str::FromStr};
+
+use crate::{
+    prelude::*,
+    vector::{self, Vector2},
+    visual::{Frame, Simulation},
+};
+
+pub const INPUT: &str = include_str!("../../input/2022/day9.txt");
+
+pub fn parse(input: &str) -> Result<Vec<Instruction>> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
+pub fn part1(instructions: &[Instruction]) -> Result<Answer> {
+    let mut world = World::new();
+
+    for instruction in instructions {
+        world.tick(instruction);
+    }
+
+    Ok(world.touched.len().into())
+}
+
+pub fn part2(instructions: &[Instruction]) -> Result<Answer> {
+    let mut world = LongerRopeWorld::new();
+
+    for instruction in instructions {
+        world.tick(instruction);
+    }
+
+    Ok(world.touched.len().into())
+}
+
+/// A rope being dragged around by its head, one cell at a time.
+pub trait Rope {
+    fn step(&mut self, direction: &Vector2);
+
+    /// Every knot, head first.
+    fn knots(&self) -> Vec<Vector2>;
+
+    /// Every position the tail has been in.
+    fn touched(&self) -> &HashSet<Vector2>;
+}
+
+pub struct LongerRopeWorld {
+    pub touched: HashSet<Vector2>,
+    rope: [Vector2; 10],
+}
+
+impl LongerRopeWorld {
+    pub fn new() -> Self {
+        Self {
+            touched: HashSet::from([vector::ZERO]),
+            rope: [vector::ZERO; 10],
+        }
+    }
+
+    pub fn tick(&mut self, i: &Instruction) {
+        (0..i.amount).for_each(|_| self.step(&i.direction));
+    }
+}
+
+impl Rope for LongerRopeWorld {
+    fn step(&mut self, direction: &Vector2) {
+        let mut new_rope = [vector::ZERO; 10];
+
+        new_rope[0] = &self.rope[0] + direction;
+
+        self.rope
+            .iter()
+            .enumerate()
+            .skip(1)
+            .for_each(|(idx, knot)| {
+                let parent = new_rope[idx - 1];
+
+                new_rope[idx] = if !knot.touching(&parent) {
+                    knot.move_towards(&parent)
+                } else {
+                    *knot
+                }
+            });
+        self.touched.insert(new_rope[9]);
+
+        self.rope = new_rope;
+    }
+
+    fn knots(&self) -> Vec<Vector2> {
+        self.rope.to_vec()
+    }
+
+    fn touched(&self) -> &HashSet<Vector2> {
+        &self.touched
+    }
+}
+
+impl Default for LongerRopeWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct World {
+    pub touched: HashSet<Vector2>,
+    head: Vector2,
+    tail: Vector2,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            touched: HashSet::from([vector::ZERO]),
+            head: vector::ZERO,
+            tail: vector::ZERO,
+        }
+    }
+
+    pub fn tick(&mut self, i: &Instruction) {
+        (0..i.amount).for_each(|_| self.step(&i.direction))
+    }
+}
+
+impl Rope for World {
+    fn step(&mut self, direction: &Vector2) {
+        self.head = &self.head + direction;
+
+        if !self.tail.touching(&self.head) {
+            self.tail = self.tail.move_towards(&self.head);
+            self.touched.insert(self.tail);
+        }
+    }
+
+    fn knots(&self) -> Vec<Vector2> {
+        vec![self.head, self.tail]
+    }
+
+    fn touched(&self) -> &HashSet<Vector2> {
+        &self.touched
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn simulation(instructions: &[Instruction], part: u8) -> Result<Box<dyn Simulation>> {
+    let instructions = instructions.to_vec();
+
+    Ok(match part {
+        1 => Box::new(RopeSimulation::new(World::new(), instructions)),
+        2 => Box::new(RopeSimulation::new(LongerRopeWorld::new(), instructions)),
+        _ => bail!("There is no part {part}"),
+    })
+}
+
+/// Moves the rope a single cell per step, drawing it the way the puzzle does:
+/// the head as `H`, the other knots by their number (or `T` for a rope of two),
+/// the start as `s` and the cells the tail has been in as `#`.
+pub struct RopeSimulation<R> {
+    rope: R,
+    instructions: Vec<Instruction>,
+    current: usize,
+    moved: usize,
+}
+
+impl<R: Rope> RopeSimulation<R> {
+    pub fn new(rope: R, instructions: Vec<Instruction>) -> Self {
+        Self {
+            rope,
+            instructions,
+            current: 0,
+            moved: 0,
+        }
+    }
+
+    fn knot_char(idx: usize, knots: usize) -> char {
+        match idx {
+            0 => 'H',
+            1 if knots == 2 => 'T',
+            _ => char::from_digit(idx as u32, 10).unwrap_or('*'),
+        }
+    }
+}
+
+impl<R: Rope> Simulation for RopeSimulation<R> {
+    fn step(&mut self) -> bool {
+        while self
+            .instructions
+            .get(self.current)
+            .is_some_and(|i| self.moved == i.amount)
+        {
+            self.current += 1;
+            self.moved = 0;
+        }
+
+        let Some(instruction) = self.instructions.get(self.current) else {
+            return false;
+        };
+
+        self.rope.step(&instruction.direction);
+        self.moved += 1;
+
+        true
+    }
+
+    fn frame(&self) -> Frame {
+        let knots = self.rope.knots();
+        let touched = self.rope.touched();
+
+        let cells = || knots.iter().chain(touched).chain([&vector::ZERO]);
+        let (min_x, max_x) = cells().map(|v| v.0).minmax().into_option().unwrap();
+        let (min_y, max_y) = cells().map(|v| v.1).minmax().into_option().unwrap();
+
+        // north is up, so the rows go from the largest y down
+        let grid = (min_y..=max_y)
+            .rev()
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| {
+                        let cell = Vector2(x, y);
+
+                        match knots.iter().position(|knot| *knot == cell) {
+                            Some(idx) => Self::knot_char(idx, knots.len()),
+                            None if cell == vector::ZERO => 's',
+                            None if touched.contains(&cell) => '#',
+                            None => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let head = knots[0];
+
+        Frame {
+            title: format!(
+                "instruction {} of {}, tail visited {} positions",
+                (self.current + 1).min(self.instructions.len()),
+                self.instructions.len(),
+                touched.len()
+            ),
+            grid,
+            focus: Some(((max_y - head.1) as usize, (head.0 - min_x) as usize)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Instruction {
+    pub direction: Vector2,
+    pub amount: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (first, second) = s.split_once(' ').ok_or_else(|| anyhow!("can't parse"))?;
+
+        let amount = second.parse::<usize>()?;
+
+        let direction = match first {
+            "U" => vector::NORTH,
+            "D" => vector::SOUTH,
+            "L" => vector::WEST,
+            "R" => vector::EAST,
+            _ => bail!("Unknown direction {first}"),
+        };
+
+        Ok(Self { amount, direction })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::snapshot::assert_snapshot;
+    use test_case::test_case;
+
+    #[test]
+    fn test_task_1() {
+        let input = include_str!("../../input/2022/day9_example.txt");
+
+        assert_eq!(part1(&parse(input).unwrap()).unwrap(), Answer::Integer(13));
+    }
+
+    #[test]
+    fn test_task_2_small() {
+        let input = include_str!("../../input/2022/day9_example.txt");
+
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), Answer::Integer(1));
+    }
+
+    #[test]
+    fn test_task_2_large() {
+        let input = include_str!("../../input/2022/day9_example_large.txt");
+
+        assert_eq!(part2(&parse(input).unwrap()).unwrap(), Answer::Integer(36));
+    }
+
+    #[test]
+    fn test_simulation_frames() {
+        let input = include_str!("../../input/2022/day9_example.txt");
+        let mut simulation = simulation(&parse(input).unwrap(), 2).unwrap();
+
+        assert_eq!(simulation.frame().grid, vec!["H"]);
+
+        assert!(simulation.step());
+        assert_eq!(simulation.frame().grid, vec!["1H"]);
+
+        assert!(simulation.step());
+        assert_eq!(simulation.frame().grid, vec!["21H"]);
+    }
+
+    #[test]
+    fn test_simulation_final_frame() {
+        let input = include_str!("../../input/2022/day9_example_large.txt");
+        let mut simulation = simulation(&parse(input).unwrap(), 2).unwrap();
+
+        while simulation.step() {}
+
+        assert_snapshot(
+            "2022/day9_example_large_part2",
+            &simulation.frame().to_string(),
+        );
+    }
+
+    #[test_case(1, 13)]
+    #[test_case(2, 1)]
+    fn test_simulation_matches_parts(part: u8, visited: usize) {
+        let input = include_str!("../../input/2022/day9_example.txt");
+        let instructions = parse(input).unwrap();
+        let mut simulation = simulation(&instructions, part).unwrap();
+
+        let mut steps = 0;
+        while simulation.step() {
+            steps += 1;
+        }
+
+        assert_eq!(steps, instructions.iter().map(|i| i.amount).sum::<usize>());
+        assert!(simulation
+            .frame()
+            .title
+            .ends_with(&format!("tail visited {visited} positions")));
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "y2022-day1")]
+pub mod day1;
+#[cfg(feature = "y2022-day10")]
+pub mod day10;
+#[cfg(feature = "y2022-day11")]
+pub mod day11;
+#[cfg(feature = "y2022-day12")]
+pub mod day12;
+#[cfg(feature = "y2022-day13")]
+pub mod day13;
+#[cfg(feature = "y2022-day14")]
+pub mod day14;
+#[cfg(feature = "y2022-day15")]
+pub mod day15;
+#[cfg(feature = "y2022-day16")]
+pub mod day16;
+#[cfg(feature = "y2022-day2")]
+pub mod day2;
+#[cfg(feature = "y2022-day3")]
+pub mod day3;
+#[cfg(feature = "y2022-day4")]
+pub mod day4;
+#[cfg(feature = "y2022-day5")]
+pub mod day5;
+#[cfg(feature = "y2022-day6")]
+pub mod day6;
+#[cfg(feature = "y2022-day7")]
+pub mod day7;
+#[cfg(feature = "y2022-day8")]
+pub mod day8;
+#[cfg(feature = "y2022-day9")]
+pub mod day9;
+
+#[allow(unused_imports)] // when building without any days
+use crate::registry::{day, Year};
+
+pub const YEAR: Year = Year {
+    year: 2022,
+    answers: include_str!("../../input/2022/answers.json"),
+    examples: include_str!("../../input/2022/examples.json"),
+    days: &[
+        #[cfg(feature = "y2022-day1")]
+        day!(2022, 1, day1, params),
+        #[cfg(feature = "y2022-day2")]
+        day!(2022, 2, day2, params),
+        #[cfg(feature = "y2022-day3")]
+        day!(2022, 3, day3, params),
+        #[cfg(feature = "y2022-day4")]
+        day!(2022, 4, day4),
+        #[cfg(feature = "y2022-day5")]
+        day!(2022, 5, day5, visualise),
+        #[cfg(feature = "y2022-day6")]
+        day!(2022, 6, day6, params),
+        #[cfg(feature = "y2022-day7")]
+        day!(2022, 7, day7, params),
+        #[cfg(feature = "y2022-day8")]
+        day!(2022, 8, day8),
+        #[cfg(feature = "y2022-day9")]
+        day!(2022, 9, day9, visualise),
+        #[cfg(feature = "y2022-day10")]
+        day!(2022, 10, day10),
+        #[cfg(feature = "y2022-day11")]
+        day!(2022, 11, day11, params),
+        #[cfg(feature = "y2022-day12")]
+        day!(2022, 12, day12),
+        #[cfg(feature = "y2022-day13")]
+        day!(2022, 13, day13),
+        #[cfg(feature = "y2022-day14")]
+        day!(2022, 14, day14, params, visualise),
+        #[cfg(feature = "y2022-day15")]
+        day!(2022, 15, day15, params),
+        #[cfg(feature = "y2022-day16")]
+        day!(2022, 16, day16, params),
+    ],
+};